@@ -13,6 +13,9 @@ def read_kmz(path: str) -> pl.DataFrame:
 
     This only captures the name and description columns.
     It also ignores any coordinate dimensions above 2.
+    Each placemark is one row of a full GEOMETRY struct, LinearRings are
+    written as LINESTRING and a MultiGeometry mixing kinds as a
+    GEOMETRYCOLLECTION.
 
     Args:
         path (str): Path to file
@@ -23,11 +26,228 @@ def read_kmz(path: str) -> pl.DataFrame:
     return geopl.read_kmz(path)
 
 
+_COORD = pl.Array(pl.Float64, 2)
+GEOMETRY_DTYPES = {
+    "POINT": _COORD,
+    "MULTIPOINT": pl.List(_COORD),
+    "LINESTRING": pl.List(_COORD),
+    "MULTILINESTRING": pl.List(pl.List(_COORD)),
+    "POLYGON": pl.List(pl.List(_COORD)),
+    "MULTIPOLYGON": pl.List(pl.List(pl.List(_COORD))),
+    "LINE": pl.Array(_COORD, 2),
+    "RECT": pl.Array(_COORD, 2),
+    "TRIANGLE": pl.Array(_COORD, 3),
+}
+GEOMETRY_DTYPES["GEOMETRYCOLLECTION"] = pl.List(pl.Struct(GEOMETRY_DTYPES))
+
+
+def _crs_suffix(crs: str | int | None) -> str | None:
+    """EPSG codes can be given as ints, 4326 becomes "EPSG:4326"."""
+    if crs is None:
+        return None
+    if isinstance(crs, int):
+        return f"EPSG:{crs}"
+    return crs
+
+
+def _origin_kwargs(origin: str | tuple[float, float]) -> dict:
+    """An origin is "centroid", "center" or an (x, y) coordinate."""
+    if isinstance(origin, str):
+        return {"origin": origin, "origin_point": None}
+    x, y = origin
+    return {"origin": "point", "origin_point": [float(x), float(y)]}
+
+
+def _value_expr(value: float | str | pl.Expr) -> pl.Expr:
+    """A number, a column name or an expression as an expression."""
+    if isinstance(value, pl.Expr):
+        return value
+    if isinstance(value, str):
+        return pl.col(value)
+    return pl.lit(float(value))
+
+
+def geometry_literal(
+    geometry_type: str, coordinates, crs: str | int | None = None
+) -> pl.Expr:
+    """
+    Make a single geometry that can be used as the `other` input of binary expressions.
+
+    The literal is broadcast against every row of the geometry column.
+
+    Args:
+        geometry_type (str): One of POINT, MULTIPOINT, LINESTRING, MULTILINESTRING, POLYGON,
+            MULTIPOLYGON, LINE, RECT, TRIANGLE
+        coordinates: Nested lists of (x, y) pairs matching the geometry type, for example
+            `[[(0, 0), (1, 0), (1, 1), (0, 0)]]` for a POLYGON
+        crs: CRS of the coordinates, like "EPSG:4326" or 4326. Without one the literal
+            takes on the CRS of the column it is compared to.
+
+    Returns:
+        Expr
+    """
+    geometry_type = geometry_type.upper()
+    crs = _crs_suffix(crs)
+    field = geometry_type if crs is None else f"{geometry_type}:{crs}"
+    dtype = pl.Struct({field: GEOMETRY_DTYPES[geometry_type]})
+    return pl.lit(pl.Series("GEOMETRY", [{field: coordinates}], dtype=dtype))
+
+
+def sjoin_nearest(
+    left: pl.DataFrame,
+    right: pl.DataFrame,
+    k: int = 1,
+    max_distance: float | None = None,
+    distance_col: str | None = "dist",
+    metric: str = "euclidean",
+    how: str = "inner",
+    left_on: str = "GEOMETRY",
+    right_on: str = "GEOMETRY",
+    suffix: str = "_right",
+) -> pl.DataFrame:
+    """
+    Join every left row to its k nearest right rows.
+
+    The right geometries go into an R-tree, so each left row only measures the
+    distances to the right geometries around it. Matches come out closest first.
+
+    Args:
+        left: DataFrame with a GEOMETRY column
+        right: DataFrame with a GEOMETRY column, in the same CRS as left
+        k: Number of right rows to match to each left row
+        max_distance: Right rows further away than this don't match
+        distance_col: Name of the column holding the distances, None leaves it out
+        metric: "euclidean" measures in the units of the coordinates. "haversine",
+            "geodesic", "vincenty" and "rhumb" take lon/lat coordinates and measure in
+            metres, like `Geo.distance`
+        how: "inner" keeps matched rows, "left" also keeps unmatched left rows with
+            nulls for the right columns
+        left_on: The GEOMETRY column of left
+        right_on: The GEOMETRY column of right
+        suffix: Appended to right column names that are already in left
+
+    Returns:
+        DataFrame with the left columns, the right columns and the distance
+    """
+    return geopl.sjoin_nearest(
+        left,
+        right,
+        k,
+        None if max_distance is None else float(max_distance),
+        distance_col,
+        metric,
+        how,
+        left_on,
+        right_on,
+        suffix,
+    )
+
+
+class SpatialIndex:
+    """
+    An R-tree over the bounding boxes of a GEOMETRY column, build it once and query it
+    many times. Queries return row numbers of the indexed column.
+
+    Null and empty geometries are never returned. Matches are by bounding box, so they
+    are candidates to be checked with a predicate like `geo.intersects`.
+    """
+
+    def __init__(self, data: pl.DataFrame | pl.Series, column: str = "GEOMETRY"):
+        """
+        Args:
+            data: A GEOMETRY Series, or a DataFrame holding one
+            column: The GEOMETRY column when data is a DataFrame
+        """
+        if isinstance(data, pl.DataFrame):
+            data = data.get_column(column)
+        self.__index = geopl.SpatialIndex(data)
+
+    def __len__(self) -> int:
+        return len(self.__index)
+
+    @property
+    def crs(self) -> str | None:
+        """The CRS of the indexed column."""
+        return self.__index.crs
+
+    def query(self, geometries: pl.Series | pl.Expr) -> pl.DataFrame:
+        """
+        Find the indexed rows whose bounding boxes intersect those of `geometries`.
+
+        Args:
+            geometries: A GEOMETRY Series or an expression that makes one, like a
+                `geometry_literal`
+
+        Returns:
+            DataFrame with an `input_index` column, the row of `geometries`, and a
+            `tree_index` column, the matching row of the index
+        """
+        if isinstance(geometries, pl.Expr):
+            geometries = pl.select(geometries).to_series()
+        return self.__index.query(geometries)
+
+    def query_envelope(
+        self, minx: float, miny: float, maxx: float, maxy: float
+    ) -> pl.Series:
+        """Rows whose bounding boxes intersect the envelope, in no particular order."""
+        return self.__index.query_envelope(minx, miny, maxx, maxy)
+
+    def nearest(self, x: float, y: float, k: int = 1) -> pl.Series:
+        """The `k` rows whose bounding boxes are closest to the point, closest first."""
+        return self.__index.nearest(x, y, k)
+
+
+def sjoin(
+    left: pl.DataFrame,
+    right: pl.DataFrame,
+    predicate: str = "intersects",
+    how: str = "inner",
+    left_on: str = "GEOMETRY",
+    right_on: str = "GEOMETRY",
+    suffix: str = "_right",
+) -> pl.DataFrame:
+    """
+    Join the rows of two DataFrames whose geometries satisfy a predicate.
+
+    The right geometries go into an R-tree to find candidates, then the predicate is
+    tested exactly on each candidate pair. Rows come out in left order.
+
+    Args:
+        left: DataFrame with a GEOMETRY column
+        right: DataFrame with a GEOMETRY column, in the same CRS as left
+        predicate: How the left geometry relates to the right one, one of intersects,
+            contains, within, touches, crosses, overlaps, covers, covered_by
+        how: "inner" keeps matched rows, "left" also keeps unmatched left rows with
+            nulls for the right columns
+        left_on: The GEOMETRY column of left
+        right_on: The GEOMETRY column of right
+        suffix: Appended to right column names that are already in left
+
+    Returns:
+        DataFrame with the left columns followed by the right columns
+    """
+    return geopl.sjoin(left, right, predicate, how, left_on, right_on, suffix)
+
+
+@pl.api.register_expr_namespace("geo")
 class Geo:
-    def __init__(self, geometry_column: str, func_as_output=True):
+    """
+    Geometry expressions on a GEOMETRY struct column.
+
+    Expressions that return geometries produce a full GEOMETRY struct so they can be
+    chained, either with `Geo(expr)` or through the `geo` namespace on expressions, for
+    example `pl.col("GEOMETRY").geo.buffer(10).geo.centroid()`.
+    """
+
+    def __init__(self, geometry_column: str | pl.Expr, func_as_output=True):
         self.__column = geometry_column
         self.__func_as_output = func_as_output
 
+    def __expr(self) -> pl.Expr:
+        if isinstance(self.__column, str):
+            return pl.col(self.__column)
+        return self.__column
+
     def set_func_as_output(self, func_as_output):
         """
         Method to change the naming behavior of methods called from this class.
@@ -37,7 +257,7 @@ class Geo:
         """
         self.__func_as_output = func_as_output
 
-    def change_column(self, column: str):
+    def change_column(self, column: str | pl.Expr):
         """
         By default the class uses the column "GEOMETRY".
 
@@ -49,6 +269,90 @@ class Geo:
         Check which column the class will operate on."""
         return self.__column
 
+    def crs(self) -> pl.Expr:
+        """
+        The CRS of the geometry column, like "EPSG:4326", or null if it doesn't have one.
+
+        The CRS is the suffix of the GEOMETRY field names, for example "POLYGON:EPSG:4326".
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="crs",
+            args=[expr],
+            is_elementwise=False,
+            returns_scalar=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("crs")
+        else:
+            return plugin
+
+    def set_crs(self, crs: str | int | None) -> pl.Expr:
+        """
+        Label the geometry column with a CRS without changing any coordinates.
+
+        Args:
+            crs: The new CRS, like "EPSG:3857" or 3857. None removes the CRS.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="set_crs",
+            args=[expr],
+            kwargs={"crs": _crs_suffix(crs)},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("set_crs")
+        else:
+            return plugin
+
+    def to_crs(
+        self, crs: str | int, source_crs: str | int | None = None
+    ) -> pl.Expr:
+        """
+        Reproject the geometries into another CRS.
+
+        CRSs are EPSG codes from a built-in table (lon/lat WGS84, NAD83 and ETRS89, the
+        UTM zones, Web Mercator and some national grids) or proj strings like
+        "+proj=utm +zone=33 +datum=WGS84". Lon/lat coordinates are in degrees.
+
+        Args:
+            crs: The CRS to convert to, like "EPSG:3857" or 3857.
+            source_crs: The CRS of a column that doesn't carry one. If the column has a
+                CRS this must match it.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="to_crs",
+            args=[expr],
+            kwargs={"crs": _crs_suffix(crs), "source_crs": _crs_suffix(source_crs)},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("to_crs")
+        else:
+            return plugin
+
+    def estimate_utm_crs(self) -> pl.Expr:
+        """
+        The WGS84 UTM zone of each geometry's centroid, like "EPSG:32633", or null for
+        empty geometries. The column needs a CRS.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="estimate_utm_crs",
+            args=[expr],
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("estimate_utm_crs")
+        else:
+            return plugin
+
     def geodesic_perimeter(self) -> pl.Expr:
         """
         Determine the perimeter of a geometry on an ellipsoidal model of the earth.
@@ -59,7 +363,7 @@ class Geo:
         rings. To get the perimeter of just the exterior ring of a polygon, do
         polygon.exterior().geodesic_length().
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="geodesic_perimeter",
@@ -80,7 +384,7 @@ class Geo:
         Returns:meter²
 
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="geodesic_area_signed",
@@ -109,7 +413,7 @@ class Geo:
         return value: (meter, meter²)
 
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="geodesic_area_unsigned",
@@ -121,15 +425,20 @@ class Geo:
         else:
             return plugin
 
-    def signed_area(self) -> pl.Expr:
+    def signed_area(self, auto_utm: bool = False) -> pl.Expr:
         """
         signed planar area of a geometry
+
+        Args:
+            auto_utm: Project each geometry into the UTM zone of its centroid first so the
+                area is in square metres, for lon/lat data. The column needs a CRS.
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="signed_area",
             args=[expr],
+            kwargs={"auto_utm": auto_utm},
             is_elementwise=True,
         )
         if self.__func_as_output:
@@ -137,15 +446,20 @@ class Geo:
         else:
             return plugin
 
-    def unsigned_area(self) -> pl.Expr:
+    def unsigned_area(self, auto_utm: bool = False) -> pl.Expr:
         """
         unsigned planar area of a geometry
+
+        Args:
+            auto_utm: Project each geometry into the UTM zone of its centroid first so the
+                area is in square metres, for lon/lat data. The column needs a CRS.
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="unsigned_area",
             args=[expr],
+            kwargs={"auto_utm": auto_utm},
             is_elementwise=True,
         )
         if self.__func_as_output:
@@ -153,6 +467,29 @@ class Geo:
         else:
             return plugin
 
+    def length(self, metric: str = "euclidean") -> pl.Expr:
+        """
+        Length of lines. Points have no length and polygons are measured along their
+        exterior rings, unlike `geodesic_perimeter` which includes the holes.
+
+        Args:
+            metric: "euclidean" is in the units of the coordinates. "haversine", "rhumb"
+                (on a sphere), "geodesic" and "vincenty" (on the WGS84 ellipsoid) take
+                lon/lat coordinates and return metres.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="length",
+            args=[expr],
+            kwargs={"metric": metric},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("length")
+        else:
+            return plugin
+
     def closest_point(self, *, x, y) -> pl.Expr:
         """
         The result of trying to find the closest spot on an object to a point.
@@ -166,7 +503,7 @@ class Geo:
 
         In this implementation that info is discarded.
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="closest_point",
@@ -192,7 +529,7 @@ class Geo:
 
         In this implementation that info is discarded.
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="haversine_closest_point",
@@ -215,7 +552,7 @@ class Geo:
         convex object always lies in the object. A non-convex object might have
         a centroid that is outside the object itself.
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="centroid",
@@ -223,7 +560,7 @@ class Geo:
             is_elementwise=True,
         )
         if self.__func_as_output:
-            return plugin.alias("haversine_closest_point")
+            return plugin.alias("centroid")
         else:
             return plugin
 
@@ -234,7 +571,7 @@ class Geo:
         possible, or on the edge if the geometry has zero area. A best effort will additionally
         be made to locate the point reasonably centrally.
         """
-        expr = pl.col(self.__column)
+        expr = self.__expr()
         plugin = register_plugin_function(
             plugin_path=Path(__file__).parent,
             function_name="interior_point",
@@ -242,11 +579,684 @@ class Geo:
             is_elementwise=True,
         )
         if self.__func_as_output:
-            return plugin.alias("haversine_closest_point")
+            return plugin.alias("interior_point")
+        else:
+            return plugin
+
+    def chaikin_smoothing(self, n_iterations: int) -> pl.Expr:
+        """
+        Smoothen LineString, Polygon, MultiLineString and MultiPolygon using Chaikin's
+        algorithm.
+
+        Each iteration doubles the number of vertices. Other geometry types are returned
+        unchanged.
+
+        Args:
+            n_iterations: Number of times to apply the smoothing
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="chaikin_smoothing",
+            args=[expr],
+            kwargs={"n_iterations": n_iterations},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("chaikin_smoothing")
+        else:
+            return plugin
+
+    def buffer(
+        self,
+        distance: float,
+        quad_segs: int = 8,
+        geodesic: bool = False,
+        auto_utm: bool = False,
+    ) -> pl.Expr:
+        """
+        The area within a distance of the geometry.
+
+        Every geometry type is supported and the result is a POLYGON or MULTIPOLYGON. A negative distance
+        shrinks polygons, points and lines have no area so they become empty.
+
+        Args:
+            distance: Buffer distance in the units of the coordinates, or in metres when
+                geodesic is True
+            quad_segs: Number of segments used to approximate a quarter circle
+            geodesic: Buffer lon/lat coordinates in metres, the column needs a
+                geographic CRS like EPSG:4326
+            auto_utm: Buffer each geometry in metres in the UTM zone of its centroid, the
+                result is projected back to the column's CRS. The column needs a CRS.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="buffer",
+            args=[expr],
+            kwargs={
+                "distance": float(distance),
+                "quad_segs": quad_segs,
+                "geodesic": geodesic,
+                "auto_utm": auto_utm,
+            },
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("buffer")
+        else:
+            return plugin
+
+    def simplify(self, tolerance: float, method: str = "rdp") -> pl.Expr:
+        """
+        Drop vertices from lines and polygons, keeping the geometry type of each row.
+
+        Points, LINE, RECT and TRIANGLE rows are returned unchanged.
+
+        Args:
+            tolerance: In the units of the coordinates. For "rdp" it's a distance, for
+                "vw" and "vw_preserve" it's the area of the triangle a vertex makes with
+                its neighbours
+            method: "rdp" (Ramer-Douglas-Peucker), "vw" (Visvalingam-Whyatt) or
+                "vw_preserve" (Visvalingam-Whyatt without creating self-intersections)
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="simplify",
+            args=[expr],
+            kwargs={"tolerance": float(tolerance), "method": method},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("simplify")
+        else:
+            return plugin
+
+    def convex_hull(self, aggregate: bool = False) -> pl.Expr:
+        """
+        The smallest convex POLYGON that contains the geometry.
+
+        Args:
+            aggregate: Make one hull of all the rows instead of one per row, for
+                `select` or as an aggregation in `group_by(...).agg(...)`
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="convex_hull",
+            args=[expr],
+            kwargs={"aggregate": aggregate},
+            is_elementwise=not aggregate,
+            returns_scalar=aggregate,
+        )
+        if self.__func_as_output:
+            return plugin.alias("convex_hull")
+        else:
+            return plugin
+
+    def concave_hull(self, concavity: float = 2.0, aggregate: bool = False) -> pl.Expr:
+        """
+        A POLYGON that wraps the vertices of the geometry more tightly than the convex
+        hull.
+
+        Args:
+            concavity: Lower values follow the points more closely, higher values get
+                closer to the convex hull
+            aggregate: Make one hull of all the rows instead of one per row, for
+                `select` or as an aggregation in `group_by(...).agg(...)`
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="concave_hull",
+            args=[expr],
+            kwargs={"concavity": float(concavity), "aggregate": aggregate},
+            is_elementwise=not aggregate,
+            returns_scalar=aggregate,
+        )
+        if self.__func_as_output:
+            return plugin.alias("concave_hull")
+        else:
+            return plugin
+
+    def minimum_rotated_rect(self, aggregate: bool = False) -> pl.Expr:
+        """
+        The smallest rectangle, at any angle, that contains the geometry, as a POLYGON.
+
+        Args:
+            aggregate: Make one hull of all the rows instead of one per row, for
+                `select` or as an aggregation in `group_by(...).agg(...)`
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="minimum_rotated_rect",
+            args=[expr],
+            kwargs={"aggregate": aggregate},
+            is_elementwise=not aggregate,
+            returns_scalar=aggregate,
+        )
+        if self.__func_as_output:
+            return plugin.alias("minimum_rotated_rect")
+        else:
+            return plugin
+
+    def bounds(self) -> pl.Expr:
+        """
+        The bounding box of each row as a struct of `minx`, `miny`, `maxx` and `maxy`.
+        Null and empty geometries give null.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="bounds",
+            args=[expr],
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("bounds")
+        else:
+            return plugin
+
+    def total_bounds(self) -> pl.Expr:
+        """
+        The bounding box of all the rows as a single `minx`, `miny`, `maxx`, `maxy`
+        struct, for `select` or as an aggregation in `group_by(...).agg(...)`.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="total_bounds",
+            args=[expr],
+            returns_scalar=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("total_bounds")
+        else:
+            return plugin
+
+    def envelope(self, as_polygon: bool = False) -> pl.Expr:
+        """
+        The bounding box of each row as a RECT.
+
+        Args:
+            as_polygon: Return a POLYGON instead of a RECT
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="envelope",
+            args=[expr],
+            kwargs={"as_polygon": as_polygon},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("envelope")
+        else:
+            return plugin
+
+    def densify(
+        self, max_segment_length: float, metric: str = "euclidean"
+    ) -> pl.Expr:
+        """
+        Add vertices so that no segment is longer than `max_segment_length`.
+
+        Densify long lon/lat edges with a geographic metric before `to_crs`, so they
+        follow the great circle (or rhumb line) after reprojecting. LINE rows are
+        returned as LINESTRING, RECT and TRIANGLE rows as POLYGON and points unchanged.
+
+        Args:
+            max_segment_length: In the units of the coordinates for "euclidean", in
+                metres for the others
+            metric: "euclidean", or "haversine", "geodesic" or "rhumb" for lon/lat
+                coordinates
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="densify",
+            args=[expr],
+            kwargs={"max_segment_length": float(max_segment_length), "metric": metric},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("densify")
+        else:
+            return plugin
+
+    def line_segmentize(self, n: int, metric: str = "euclidean") -> pl.Expr:
+        """
+        Split every line into `n` pieces of equal length, as a MULTILINESTRING.
+
+        Polygons are split along each of their rings and MULTILINESTRING and
+        MULTIPOLYGON rows split every part, so the result has `n` pieces per line or
+        ring. Point rows give null.
+
+        Args:
+            n: Number of pieces per line
+            metric: "euclidean", or "haversine" for lon/lat coordinates
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="line_segmentize",
+            args=[expr],
+            kwargs={"n": n, "metric": metric},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("line_segmentize")
+        else:
+            return plugin
+
+    def translate(self, dx: float = 0.0, dy: float = 0.0) -> pl.Expr:
+        """
+        Shift the geometry by `dx` and `dy`, in the units of the coordinates.
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="translate",
+            args=[expr],
+            kwargs={"dx": float(dx), "dy": float(dy)},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("translate")
+        else:
+            return plugin
+
+    def rotate(
+        self, angle: float, origin: str | tuple[float, float] = "centroid"
+    ) -> pl.Expr:
+        """
+        Rotate the geometry counter-clockwise.
+
+        A rotated RECT is returned as a POLYGON, other rows keep their geometry type.
+
+        Args:
+            angle: In degrees
+            origin: "centroid" or "center" (of the bounding box) of each row, or an
+                (x, y) coordinate used for every row
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="rotate",
+            args=[expr],
+            kwargs={"angle": float(angle), **_origin_kwargs(origin)},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("rotate")
+        else:
+            return plugin
+
+    def scale(
+        self,
+        sx: float,
+        sy: float | None = None,
+        origin: str | tuple[float, float] = "centroid",
+    ) -> pl.Expr:
+        """
+        Scale the geometry along x and y.
+
+        Args:
+            sx: Factor along x
+            sy: Factor along y, defaults to `sx`
+            origin: "centroid" or "center" (of the bounding box) of each row, or an
+                (x, y) coordinate used for every row
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="scale",
+            args=[expr],
+            kwargs={
+                "x": float(sx),
+                "y": float(sx if sy is None else sy),
+                **_origin_kwargs(origin),
+            },
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("scale")
+        else:
+            return plugin
+
+    def skew(
+        self,
+        xs: float = 0.0,
+        ys: float = 0.0,
+        origin: str | tuple[float, float] = "centroid",
+    ) -> pl.Expr:
+        """
+        Shear the geometry by angles along x and y.
+
+        A skewed RECT is returned as a POLYGON, other rows keep their geometry type.
+
+        Args:
+            xs: Angle in degrees along x
+            ys: Angle in degrees along y
+            origin: "centroid" or "center" (of the bounding box) of each row, or an
+                (x, y) coordinate used for every row
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="skew",
+            args=[expr],
+            kwargs={"x": float(xs), "y": float(ys), **_origin_kwargs(origin)},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("skew")
+        else:
+            return plugin
+
+    def affine_transform(self, matrix: list[float]) -> pl.Expr:
+        """
+        Apply an affine transformation.
+
+        A RECT that gets rotated or skewed is returned as a POLYGON, other rows keep
+        their geometry type.
+
+        Args:
+            matrix: `[a, b, d, e, xoff, yoff]`, giving `x' = a * x + b * y + xoff` and
+                `y' = d * x + e * y + yoff`
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="affine_transform",
+            args=[expr],
+            kwargs={"matrix": [float(v) for v in matrix]},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("affine_transform")
+        else:
+            return plugin
+
+    def __binary(
+        self, function_name: str, other: str | pl.Expr, kwargs: dict | None = None
+    ) -> pl.Expr:
+        expr = self.__expr()
+        if isinstance(other, str):
+            other = pl.col(other)
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name=function_name,
+            args=[expr, other],
+            kwargs=kwargs,
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias(function_name)
         else:
             return plugin
 
+    def line_interpolate_point(
+        self, fraction: float | str | pl.Expr, normalized: bool = True
+    ) -> pl.Expr:
+        """
+        The POINT at a position along each LINESTRING.
+
+        Positions before the start or past the end are clamped to the ends. Rows that
+        aren't LINESTRING or LINE give null.
+
+        Args:
+            fraction: Fraction of the length of the line, or a column name or expression
+                with one per row
+            normalized: When False, `fraction` is a distance along the line in the
+                units of its CRS, like a chainage, e.g. metres for EPSG:3857 or
+                degrees for EPSG:4326. Lines without length then give null
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="line_interpolate_point",
+            args=[expr, _value_expr(fraction)],
+            kwargs={"normalized": normalized},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("line_interpolate_point")
+        else:
+            return plugin
+
+    def line_locate_point(
+        self,
+        point: str | pl.Expr | tuple[float, float],
+        normalized: bool = True,
+    ) -> pl.Expr:
+        """
+        The position along each LINESTRING of the spot closest to a POINT.
+
+        Rows that aren't LINESTRING or LINE, or whose other geometry isn't a POINT,
+        give null.
+
+        Args:
+            point: A GEOMETRY column or expression holding POINTs, or an (x, y)
+                coordinate used for every row
+            normalized: When False, the position is a distance along the line in the
+                units of its CRS instead of a fraction of its length, e.g. metres
+                for EPSG:3857 or degrees for EPSG:4326
+        """
+        if isinstance(point, tuple):
+            point = geometry_literal("POINT", list(point))
+        return self.__binary("line_locate_point", point, {"normalized": normalized})
+
+    def line_substring(
+        self,
+        start: float | str | pl.Expr,
+        end: float | str | pl.Expr,
+        normalized: bool = True,
+    ) -> pl.Expr:
+        """
+        The part of each LINESTRING between two positions along it, as a LINESTRING.
+
+        The result runs backwards when `start` is after `end`, positions outside the
+        line are clamped to its ends. Rows that aren't LINESTRING or LINE give null.
+
+        Args:
+            start: Fraction of the length of the line, or a column name or expression
+                with one per row
+            end: Like `start`
+            normalized: When False, `start` and `end` are distances along the line in
+                the units of its CRS, e.g. metres for EPSG:3857 or degrees for
+                EPSG:4326. Lines without length then give null
+        """
+        expr = self.__expr()
+        plugin = register_plugin_function(
+            plugin_path=Path(__file__).parent,
+            function_name="line_substring",
+            args=[expr, _value_expr(start), _value_expr(end)],
+            kwargs={"normalized": normalized},
+            is_elementwise=True,
+        )
+        if self.__func_as_output:
+            return plugin.alias("line_substring")
+        else:
+            return plugin
+
+    def distance(self, other: str | pl.Expr, metric: str = "euclidean") -> pl.Expr:
+        """
+        Minimum distance between two geometries of any type.
+
+        Args:
+            other: Name of another geometry column or an expression of one, such as
+                `geometry_literal`. A single geometry is compared against every row.
+            metric: "euclidean" is in the units of the CRS, e.g. degrees for
+                EPSG:4326. "haversine", "rhumb" (on a sphere), "geodesic" and "vincenty"
+                (on the WGS84 ellipsoid) take lon/lat coordinates and return metres.
+                Vincenty gives null for nearly antipodal points where it doesn't
+                converge.
+
+        Distances between two points are exact. For other geometries the closest
+        point is found on great circles and then measured with the metric, so
+        "geodesic", "vincenty" and "rhumb" distances to lines and polygons are
+        approximate.
+        """
+        return self.__binary("distance", other, {"metric": metric})
+
+    def intersects(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the geometry shares any point with the other geometry.
+
+        Args:
+            other: Name of another geometry column or an expression of one, such as
+                `geometry_literal`. A single geometry is compared against every row.
+        """
+        return self.__binary("intersects", other)
+
+    def contains(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the other geometry is completely inside the geometry.
+
+        No points of other lie in the exterior of the geometry and at least one point of
+        the interior of other lies in the interior of the geometry. A polygon does not
+        contain a point on its boundary.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("contains", other)
+
+    def within(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the geometry is completely inside the other geometry, the inverse of contains.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("within", other)
+
+    def touches(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the geometries have at least one boundary point in common but their
+        interiors do not intersect.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("touches", other)
+
+    def crosses(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the geometries have some but not all interior points in common and the
+        intersection has a lower dimension than the larger of the two, for example a line
+        passing through a polygon.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("crosses", other)
+
+    def overlaps(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the geometries have the same dimension and share some, but not all,
+        interior points.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("overlaps", other)
+
+    def disjoint(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if the geometries have no point in common, the inverse of intersects.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("disjoint", other)
+
+    def covers(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        Checks if no point of the other geometry lies in the exterior of the geometry.
+
+        Unlike contains, a polygon covers a point on its boundary.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("covers", other)
+
+    def relate(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        The DE-9IM intersection matrix of the geometry and the other geometry.
+
+        Returns a 9 character string such as "212101212" where each character is the
+        dimension (F, 0, 1 or 2) of the intersection of the interior, boundary and exterior
+        of the geometry with the interior, boundary and exterior of the other geometry.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("relate", other)
+
+    def relate_pattern(self, other: str | pl.Expr, pattern: str) -> pl.Expr:
+        """
+        Checks if the DE-9IM intersection matrix of the two geometries matches a pattern.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+            pattern: 9 character DE-9IM pattern made of T, F, *, 0, 1 and 2. For example
+                "T*F**F***" is within.
+        """
+        return self.__binary("relate_pattern", other, {"pattern": pattern})
+
+    def intersection(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        The area shared by the geometry and the other geometry.
+
+        Only Polygon, MultiPolygon, Rect and Triangle rows are supported, other rows are null.
+        The result is always a MULTIPOLYGON in the returned GEOMETRY struct.
+
+        Args:
+            other: Name of another geometry column or an expression of one, such as
+                `geometry_literal` to clip every row to one study area.
+        """
+        return self.__binary("intersection", other)
+
+    def union(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        The area covered by either the geometry or the other geometry.
+
+        Only Polygon, MultiPolygon, Rect and Triangle rows are supported, other rows are null.
+        The result is always a MULTIPOLYGON in the returned GEOMETRY struct.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("union", other)
+
+    def difference(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        The area of the geometry that isn't covered by the other geometry.
+
+        Only Polygon, MultiPolygon, Rect and Triangle rows are supported, other rows are null.
+        The result is always a MULTIPOLYGON in the returned GEOMETRY struct.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("difference", other)
+
+    def symmetric_difference(self, other: str | pl.Expr) -> pl.Expr:
+        """
+        The area covered by exactly one of the geometry and the other geometry.
+
+        Only Polygon, MultiPolygon, Rect and Triangle rows are supported, other rows are null.
+        The result is always a MULTIPOLYGON in the returned GEOMETRY struct.
+
+        Args:
+            other: Name of another geometry column or an expression of one.
+        """
+        return self.__binary("symmetric_difference", other)
 
 geo = Geo("GEOMETRY")
 
-__all__ = ["geo", "Geo", "read_kmz"]
+__all__ = ["geo", "Geo", "read_kmz", "geometry_literal", "SpatialIndex", "sjoin", "sjoin_nearest"]
@@ -1,23 +1,57 @@
 use polars::prelude::*;
 use pyo3_polars::derive::polars_expr;
-use crate::ops::run_op_on_struct;
-use geo::{Area, Centroid, ChaikinSmoothing, InteriorPoint,HaversineClosestPoint,Distance,Closest, ClosestPoint, GeodesicArea, Point};
+use crate::ops::{
+    AutoUtm, Buffer, Metric, Origin, Reprojection, SimplifyMethod, apply_affine,
+    bounds as row_bounds, bounds_dtype, check_densify, check_segmentize, combine_crs,
+    densify as densify_geometry, dtype_crs, dtype_with_crs, envelope as envelope_of,
+    geodesic_buffer, geometry_dtype, interpolate_point, line_segmentize as segmentize_geometry,
+    locate_point, parse_crs, run_binary_geometry_op_on_structs, run_binary_op_on_structs,
+    run_geometry_agg_on_struct, run_geometry_op_on_struct, run_geometry_op_with_values_on_struct,
+    run_op_on_struct, set_crs as rename_crs, simplify as simplify_geometry, substring,
+    total_bounds as column_bounds, transform_around,
+};
+use geo::coordinate_position::CoordPos;
+use geo::dimensions::Dimensions;
+use geo::relate::IntersectionMatrix;
+use geo::{
+    AffineTransform, BooleanOps, ChaikinSmoothing, ConcaveHull, ConvexHull, CoordsIter, Geometry,
+    MinimumRotatedRect, MultiPoint, MultiPolygon, OpType, Polygon,
+};
+use geo::{Area, Centroid, InteriorPoint,HaversineClosestPoint,Intersects,Contains,Within,Relate,Closest, ClosestPoint, GeodesicArea, Point};
 use serde::Deserialize;
+use std::borrow::Cow;
 
 pub fn float_output(fields: &[Field]) -> PolarsResult<Field> {
     FieldsMapper::new(fields).map_to_float_dtype()
 }
-pub fn point_2d_output(_: &[Field]) -> PolarsResult<Field> {
-    Ok(Field::new(
-        PlSmallStr::from_static("point_2d"),
-        DataType::Array(Box::new(DataType::Float64), 2),
-    ))
+pub fn bool_output(fields: &[Field]) -> PolarsResult<Field> {
+    FieldsMapper::new(fields).with_dtype(DataType::Boolean)
+}
+pub fn string_output(fields: &[Field]) -> PolarsResult<Field> {
+    FieldsMapper::new(fields).with_dtype(DataType::String)
+}
+/// A GEOMETRY struct in the CRS of the geometry inputs.
+pub fn geometry_output(fields: &[Field]) -> PolarsResult<Field> {
+    let crs = combine_crs(
+        fields
+            .iter()
+            .map(|f| dtype_crs(f.dtype()))
+            .collect::<PolarsResult<Vec<_>>>()?,
+    )?;
+    FieldsMapper::new(fields).with_dtype(geometry_dtype(crs.as_deref()))
+}
+/// A GEOMETRY struct in the CRS of the first input, the other inputs are
+/// plain values like fractions.
+pub fn geometry_with_values_output(fields: &[Field]) -> PolarsResult<Field> {
+    let crs = dtype_crs(fields[0].dtype())?;
+    FieldsMapper::new(fields).with_dtype(geometry_dtype(crs.as_deref()))
 }
 
 #[polars_expr(output_type_func=float_output)]
 fn geodesic_perimeter(inputs: &[Series]) -> PolarsResult<Series> {
     run_op_on_struct(
-        &inputs,
+        inputs,
+        &DataType::Float64,
         |g| g.geodesic_perimeter(),
     )
 }
@@ -25,7 +59,8 @@ fn geodesic_perimeter(inputs: &[Series]) -> PolarsResult<Series> {
 #[polars_expr(output_type_func=float_output)]
 fn geodesic_area_signed(inputs: &[Series]) -> PolarsResult<Series> {
     run_op_on_struct(
-        &inputs,
+        inputs,
+        &DataType::Float64,
         |g| g.geodesic_area_signed(),
     )
 }
@@ -33,27 +68,72 @@ fn geodesic_area_signed(inputs: &[Series]) -> PolarsResult<Series> {
 #[polars_expr(output_type_func=float_output)]
 fn geodesic_area_unsigned(inputs: &[Series]) -> PolarsResult<Series> {
     run_op_on_struct(
-        &inputs,
+        inputs,
+        &DataType::Float64,
         |g| g.geodesic_area_signed(),
     )
 }
 
-#[polars_expr(output_type_func=float_output)]
-fn signed_area(inputs: &[Series]) -> PolarsResult<Series> {
-    run_op_on_struct(
-        &inputs,
-        |g| g.signed_area(),
-    )
+#[derive(Deserialize)]
+struct AreaKwargs {
+    auto_utm: bool,
 }
 
+/// The CRS auto_utm projects from, it can't guess it.
+fn auto_utm_crs(dtype: &DataType) -> PolarsResult<PlSmallStr> {
+    dtype_crs(dtype)?.ok_or_else(|| {
+        polars_err!(ComputeError: "auto_utm needs the CRS of the geometry column, use set_crs first")
+    })
+}
 
+/// Geodesic buffers take lon/lat degrees, so the column needs a geographic CRS.
+fn check_geodesic_crs(dtype: &DataType) -> PolarsResult<()> {
+    let crs = dtype_crs(dtype)?.ok_or_else(|| {
+        polars_err!(ComputeError: "geodesic buffer needs the CRS of the geometry column, use set_crs first")
+    })?;
+    polars_ensure!(
+        parse_crs(&crs)?.is_latlong(),
+        ComputeError: "geodesic buffer needs lon/lat coordinates but the CRS is {}, use to_crs first", crs
+    );
+    Ok(())
+}
+
+/// Runs a planar op on each geometry in its UTM zone so the result is in metres.
+fn run_op_in_utm<F>(inputs: &[Series], f: F) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry) -> f64 + Sync,
+{
+    let utm = AutoUtm::new(&auto_utm_crs(inputs[0].dtype())?)?;
+    run_op_on_struct(inputs, &DataType::Float64, |g| {
+        utm.project(g)
+            .map(|utm_geom| utm_geom.map(|(utm_geom, _)| f(&utm_geom)))
+    })
+}
 
 #[polars_expr(output_type_func=float_output)]
-fn unsigned_area(inputs: &[Series]) -> PolarsResult<Series> {
-    run_op_on_struct(
-        &inputs,
-        |g| g.unsigned_area(),
-    )
+fn signed_area(inputs: &[Series], kwargs: AreaKwargs) -> PolarsResult<Series> {
+    match kwargs.auto_utm {
+        true => run_op_in_utm(inputs, |g| g.signed_area()),
+        false => run_op_on_struct(inputs, &DataType::Float64, |g| g.signed_area()),
+    }
+}
+
+#[polars_expr(output_type_func=float_output)]
+fn unsigned_area(inputs: &[Series], kwargs: AreaKwargs) -> PolarsResult<Series> {
+    match kwargs.auto_utm {
+        true => run_op_in_utm(inputs, |g| g.unsigned_area()),
+        false => run_op_on_struct(inputs, &DataType::Float64, |g| g.unsigned_area()),
+    }
+}
+
+/// The WGS84 UTM zone of each geometry's centroid, like "EPSG:32633".
+#[polars_expr(output_type_func=string_output)]
+fn estimate_utm_crs(inputs: &[Series]) -> PolarsResult<Series> {
+    let utm = AutoUtm::new(&auto_utm_crs(inputs[0].dtype())?)?;
+    run_op_on_struct(inputs, &DataType::String, |g| {
+        utm.estimate(g)
+            .map(|code| code.map(|code| format!("EPSG:{}", code)))
+    })
 }
 #[derive(Deserialize)]
 struct OtherPointKwarg {
@@ -62,9 +142,9 @@ struct OtherPointKwarg {
 }
 
 
-#[polars_expr(output_type_func=point_2d_output)]
+#[polars_expr(output_type_func=geometry_output)]
 fn closest_point(inputs: &[Series], kwargs: OtherPointKwarg) -> PolarsResult<Series> {
-    run_op_on_struct(inputs, |g| {
+    run_geometry_op_on_struct(inputs, |g| {
         let other_point: Point = (kwargs.x, kwargs.y).into();
         let closest = g.closest_point(&other_point);
         match closest {
@@ -75,9 +155,9 @@ fn closest_point(inputs: &[Series], kwargs: OtherPointKwarg) -> PolarsResult<Ser
     )
 }
 
-#[polars_expr(output_type_func=point_2d_output)]
+#[polars_expr(output_type_func=geometry_output)]
 fn haversine_closest_point(inputs: &[Series], kwargs: OtherPointKwarg) -> PolarsResult<Series> {
-    run_op_on_struct(inputs, |g| {
+    run_geometry_op_on_struct(inputs, |g| {
         let other_point: Point = (kwargs.x, kwargs.y).into();
         let closest = g.haversine_closest_point(&other_point);
         match closest {
@@ -88,14 +168,751 @@ fn haversine_closest_point(inputs: &[Series], kwargs: OtherPointKwarg) -> Polars
     )
 }
 
-#[polars_expr(output_type_func=point_2d_output)]
+#[polars_expr(output_type_func=geometry_output)]
 fn centroid(inputs: &[Series]) -> PolarsResult<Series> {
-    run_op_on_struct(inputs, |g| g.centroid())
+    run_geometry_op_on_struct(inputs, |g| g.centroid())
 }
 
-#[polars_expr(output_type_func=point_2d_output)]
+#[polars_expr(output_type_func=geometry_output)]
 fn interior_point(inputs: &[Series]) -> PolarsResult<Series> {
-    run_op_on_struct(inputs, |g| g.interior_point())
+    run_geometry_op_on_struct(inputs, |g| g.interior_point())
+}
+
+#[derive(Deserialize)]
+struct MetricKwargs {
+    metric: String,
+}
+
+#[polars_expr(output_type_func=float_output)]
+fn distance(inputs: &[Series], kwargs: MetricKwargs) -> PolarsResult<Series> {
+    let metric = Metric::parse(&kwargs.metric)?;
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Float64,
+        |g, other| metric.distance(g, other),
+    )
+}
+
+#[polars_expr(output_type_func=float_output)]
+fn length(inputs: &[Series], kwargs: MetricKwargs) -> PolarsResult<Series> {
+    let metric = Metric::parse(&kwargs.metric)?;
+    run_op_on_struct(
+        inputs,
+        &DataType::Float64,
+        |g| metric.length(g),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn intersects(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.intersects(other),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn contains(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.contains(other),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn within(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.is_within(other),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn touches(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.relate(other).is_touches(),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn crosses(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.relate(other).is_crosses(),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn overlaps(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.relate(other).is_overlaps(),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn disjoint(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| !g.intersects(other),
+    )
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn covers(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.relate(other).is_covers(),
+    )
+}
+
+fn de9im_string(matrix: &IntersectionMatrix) -> String {
+    let positions = [CoordPos::Inside, CoordPos::OnBoundary, CoordPos::Outside];
+    positions
+        .iter()
+        .flat_map(|lhs| positions.iter().map(move |rhs| matrix.get(*lhs, *rhs)))
+        .map(|dim| match dim {
+            Dimensions::Empty => 'F',
+            Dimensions::ZeroDimensional => '0',
+            Dimensions::OneDimensional => '1',
+            Dimensions::TwoDimensional => '2',
+        })
+        .collect()
+}
+
+#[polars_expr(output_type_func=string_output)]
+fn relate(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::String,
+        |g, other| de9im_string(&g.relate(other)),
+    )
+}
+
+#[derive(Deserialize)]
+struct RelatePatternKwargs {
+    pattern: String,
+}
+
+#[polars_expr(output_type_func=bool_output)]
+fn relate_pattern(inputs: &[Series], kwargs: RelatePatternKwargs) -> PolarsResult<Series> {
+    // check the pattern once up front rather than failing on every row
+    if let Err(e) = IntersectionMatrix::empty().matches(&kwargs.pattern) {
+        polars_bail!(ComputeError: "bad DE-9IM pattern {}: {}", kwargs.pattern, e)
+    }
+    run_binary_op_on_structs(
+        inputs,
+        &DataType::Boolean,
+        |g, other| g.relate(other).matches(&kwargs.pattern).ok(),
+    )
+}
+
+/// The areal geometries that can be overlaid, RECT and TRIANGLE as a POLYGON.
+fn areal(g: &Geometry) -> Option<Cow<'_, Geometry>> {
+    match g {
+        Geometry::Polygon(_) | Geometry::MultiPolygon(_) => Some(Cow::Borrowed(g)),
+        Geometry::Rect(r) => Some(Cow::Owned(r.to_polygon().into())),
+        Geometry::Triangle(t) => Some(Cow::Owned(t.to_polygon().into())),
+        _ => None,
+    }
+}
+
+/// Overlay of two areal geometries, rows that aren't a Polygon, MultiPolygon,
+/// Rect or Triangle are null.
+fn overlay(g: &Geometry, other: &Geometry, op: OpType) -> Option<MultiPolygon> {
+    let (g, other) = (areal(g)?, areal(other)?);
+    match (g.as_ref(), other.as_ref()) {
+        (Geometry::Polygon(a), Geometry::Polygon(b)) => Some(a.boolean_op(b, op)),
+        (Geometry::Polygon(a), Geometry::MultiPolygon(b)) => Some(a.boolean_op(b, op)),
+        (Geometry::MultiPolygon(a), Geometry::Polygon(b)) => Some(a.boolean_op(b, op)),
+        (Geometry::MultiPolygon(a), Geometry::MultiPolygon(b)) => Some(a.boolean_op(b, op)),
+        _ => None,
+    }
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn intersection(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_geometry_op_on_structs(
+        inputs,
+        |g, other| overlay(g, other, OpType::Intersection),
+    )
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn union(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_geometry_op_on_structs(
+        inputs,
+        |g, other| overlay(g, other, OpType::Union),
+    )
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn difference(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_geometry_op_on_structs(
+        inputs,
+        |g, other| overlay(g, other, OpType::Difference),
+    )
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn symmetric_difference(inputs: &[Series]) -> PolarsResult<Series> {
+    run_binary_geometry_op_on_structs(
+        inputs,
+        |g, other| overlay(g, other, OpType::Xor),
+    )
+}
+
+#[derive(Deserialize)]
+struct BufferKwargs {
+    distance: f64,
+    quad_segs: usize,
+    geodesic: bool,
+    auto_utm: bool,
+}
+
+fn polygon_or_multi(mut mp: MultiPolygon) -> Geometry {
+    match mp.0.len() {
+        1 => Geometry::Polygon(mp.0.remove(0)),
+        _ => Geometry::MultiPolygon(mp),
+    }
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn buffer(inputs: &[Series], kwargs: BufferKwargs) -> PolarsResult<Series> {
+    polars_ensure!(
+        !(kwargs.geodesic && kwargs.auto_utm),
+        InvalidOperation: "buffer takes geodesic or auto_utm, not both"
+    );
+    if kwargs.auto_utm {
+        let utm = AutoUtm::new(&auto_utm_crs(inputs[0].dtype())?)?;
+        return run_geometry_op_on_struct(inputs, |g| {
+            let Some((utm_geom, code)) = utm.project(g)? else {
+                return Ok(None);
+            };
+            let buffered = polygon_or_multi(utm_geom.buffer(kwargs.distance, kwargs.quad_segs));
+            utm.unproject(buffered, code).map(Some)
+        });
+    }
+    if kwargs.geodesic {
+        check_geodesic_crs(inputs[0].dtype())?;
+    }
+    run_geometry_op_on_struct(inputs, |g| {
+        let buffered = if kwargs.geodesic {
+            geodesic_buffer(g, kwargs.distance, kwargs.quad_segs)
+        } else {
+            Some(g.buffer(kwargs.distance, kwargs.quad_segs))
+        };
+        buffered.map(polygon_or_multi)
+    })
+}
+
+#[derive(Deserialize)]
+struct ChaikinSmoothingKwargs {
+    n_iterations: usize,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn chaikin_smoothing(inputs: &[Series], kwargs: ChaikinSmoothingKwargs) -> PolarsResult<Series> {
+    run_geometry_op_on_struct(
+        inputs,
+        |g| g.chaikin_smoothing(kwargs.n_iterations),
+    )
+}
+
+#[derive(Deserialize)]
+struct SimplifyKwargs {
+    tolerance: f64,
+    method: String,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn simplify(inputs: &[Series], kwargs: SimplifyKwargs) -> PolarsResult<Series> {
+    let method = SimplifyMethod::parse(&kwargs.method)?;
+    run_geometry_op_on_struct(
+        inputs,
+        |g| simplify_geometry(g, kwargs.tolerance, method),
+    )
+}
+
+#[derive(Deserialize)]
+struct DensifyKwargs {
+    max_segment_length: f64,
+    metric: String,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn densify(inputs: &[Series], kwargs: DensifyKwargs) -> PolarsResult<Series> {
+    let metric = Metric::parse(&kwargs.metric)?;
+    check_densify(kwargs.max_segment_length, metric)?;
+    run_geometry_op_on_struct(
+        inputs,
+        |g| densify_geometry(g, kwargs.max_segment_length, metric),
+    )
+}
+
+#[derive(Deserialize)]
+struct SegmentizeKwargs {
+    n: usize,
+    metric: String,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn line_segmentize(inputs: &[Series], kwargs: SegmentizeKwargs) -> PolarsResult<Series> {
+    let metric = Metric::parse(&kwargs.metric)?;
+    check_segmentize(kwargs.n, metric)?;
+    run_geometry_op_on_struct(
+        inputs,
+        |g| segmentize_geometry(g, kwargs.n, metric),
+    )
+}
+
+#[derive(Deserialize)]
+struct LinearKwargs {
+    normalized: bool,
+}
+
+/// Takes the geometries and a Float64 position per row.
+#[polars_expr(output_type_func=geometry_with_values_output)]
+fn line_interpolate_point(inputs: &[Series], kwargs: LinearKwargs) -> PolarsResult<Series> {
+    run_geometry_op_with_values_on_struct(inputs, |g, values| {
+        interpolate_point(g, values[0], kwargs.normalized)
+    })
+}
+
+/// Takes the lines and a geometry column of points.
+#[polars_expr(output_type_func=float_output)]
+fn line_locate_point(inputs: &[Series], kwargs: LinearKwargs) -> PolarsResult<Series> {
+    run_binary_op_on_structs(inputs, &DataType::Float64, |g, point| {
+        locate_point(g, point, kwargs.normalized)
+    })
+}
+
+/// Takes the geometries and Float64 start and end positions per row.
+#[polars_expr(output_type_func=geometry_with_values_output)]
+fn line_substring(inputs: &[Series], kwargs: LinearKwargs) -> PolarsResult<Series> {
+    run_geometry_op_with_values_on_struct(inputs, |g, values| {
+        substring(g, values[0], values[1], kwargs.normalized)
+    })
+}
+
+#[derive(Deserialize)]
+struct TranslateKwargs {
+    dx: f64,
+    dy: f64,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn translate(inputs: &[Series], kwargs: TranslateKwargs) -> PolarsResult<Series> {
+    let transform = AffineTransform::translate(kwargs.dx, kwargs.dy);
+    run_geometry_op_on_struct(inputs, |g| apply_affine(g, &transform))
+}
+
+#[derive(Deserialize)]
+struct RotateKwargs {
+    angle: f64,
+    origin: String,
+    origin_point: Option<[f64; 2]>,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn rotate(inputs: &[Series], kwargs: RotateKwargs) -> PolarsResult<Series> {
+    let origin = Origin::parse(&kwargs.origin, kwargs.origin_point)?;
+    run_geometry_op_on_struct(inputs, |g| {
+        transform_around(g, origin, |o| AffineTransform::rotate(kwargs.angle, o))
+    })
+}
+
+/// Scale factors or skew angles along x and y, around an origin.
+#[derive(Deserialize)]
+struct AxesKwargs {
+    x: f64,
+    y: f64,
+    origin: String,
+    origin_point: Option<[f64; 2]>,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn scale(inputs: &[Series], kwargs: AxesKwargs) -> PolarsResult<Series> {
+    let origin = Origin::parse(&kwargs.origin, kwargs.origin_point)?;
+    run_geometry_op_on_struct(inputs, |g| {
+        transform_around(g, origin, |o| AffineTransform::scale(kwargs.x, kwargs.y, o))
+    })
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn skew(inputs: &[Series], kwargs: AxesKwargs) -> PolarsResult<Series> {
+    let origin = Origin::parse(&kwargs.origin, kwargs.origin_point)?;
+    run_geometry_op_on_struct(inputs, |g| {
+        transform_around(g, origin, |o| AffineTransform::skew(kwargs.x, kwargs.y, o))
+    })
+}
+
+#[derive(Deserialize)]
+struct AffineKwargs {
+    matrix: Vec<f64>,
+}
+
+/// `matrix` is `[a, b, d, e, xoff, yoff]`, so `x' = a * x + b * y + xoff` and
+/// `y' = d * x + e * y + yoff`.
+#[polars_expr(output_type_func=geometry_output)]
+fn affine_transform(inputs: &[Series], kwargs: AffineKwargs) -> PolarsResult<Series> {
+    let &[a, b, d, e, xoff, yoff] = kwargs.matrix.as_slice() else {
+        polars_bail!(
+            InvalidOperation: "affine_transform takes 6 values [a, b, d, e, xoff, yoff], got {}",
+            kwargs.matrix.len()
+        );
+    };
+    let transform = AffineTransform::new(a, b, xoff, d, e, yoff);
+    run_geometry_op_on_struct(inputs, |g| apply_affine(g, &transform))
+}
+
+#[derive(Deserialize)]
+struct HullKwargs {
+    #[serde(default)]
+    concavity: f64,
+    aggregate: bool,
+}
+
+/// Hulls are per row, or one for the whole column or group when aggregating.
+fn run_hull<F>(inputs: &[Series], aggregate: bool, f: F) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry) -> Option<Polygon> + Sync,
+{
+    match aggregate {
+        true => run_geometry_agg_on_struct(inputs, f),
+        false => run_geometry_op_on_struct(inputs, f),
+    }
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn convex_hull(inputs: &[Series], kwargs: HullKwargs) -> PolarsResult<Series> {
+    run_hull(inputs, kwargs.aggregate, |g| {
+        (g.coords_count() > 0).then(|| g.convex_hull())
+    })
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn concave_hull(inputs: &[Series], kwargs: HullKwargs) -> PolarsResult<Series> {
+    run_hull(inputs, kwargs.aggregate, |g| {
+        let points: MultiPoint = g.exterior_coords_iter().map(Point::from).collect();
+        (!points.0.is_empty()).then(|| points.concave_hull(kwargs.concavity))
+    })
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn minimum_rotated_rect(inputs: &[Series], kwargs: HullKwargs) -> PolarsResult<Series> {
+    run_hull(inputs, kwargs.aggregate, |g| g.minimum_rotated_rect())
+}
+
+pub fn bounds_output(fields: &[Field]) -> PolarsResult<Field> {
+    FieldsMapper::new(fields).with_dtype(bounds_dtype())
+}
+
+#[polars_expr(output_type_func=bounds_output)]
+fn bounds(inputs: &[Series]) -> PolarsResult<Series> {
+    row_bounds(&inputs[0])
 }
 
+#[polars_expr(output_type_func=bounds_output)]
+fn total_bounds(inputs: &[Series]) -> PolarsResult<Series> {
+    column_bounds(&inputs[0])
+}
 
+#[derive(Deserialize)]
+struct EnvelopeKwargs {
+    as_polygon: bool,
+}
+
+#[polars_expr(output_type_func=geometry_output)]
+fn envelope(inputs: &[Series], kwargs: EnvelopeKwargs) -> PolarsResult<Series> {
+    envelope_of(&inputs[0], kwargs.as_polygon)
+}
+
+/// The CRS of a geometry column, it lives in the field names so this doesn't read any rows.
+#[polars_expr(output_type_func=string_output)]
+fn crs(inputs: &[Series]) -> PolarsResult<Series> {
+    let crs = dtype_crs(inputs[0].dtype())?;
+    Ok(Series::new(inputs[0].name().clone(), [crs.as_deref()]))
+}
+
+#[derive(Deserialize)]
+struct SetCrsKwargs {
+    crs: Option<String>,
+}
+
+fn set_crs_output(fields: &[Field], kwargs: SetCrsKwargs) -> PolarsResult<Field> {
+    let dtype = dtype_with_crs(fields[0].dtype(), kwargs.crs.as_deref())?;
+    FieldsMapper::new(fields).with_dtype(dtype)
+}
+
+#[polars_expr(output_type_func_with_kwargs=set_crs_output)]
+fn set_crs(inputs: &[Series], kwargs: SetCrsKwargs) -> PolarsResult<Series> {
+    rename_crs(&inputs[0], kwargs.crs.as_deref())
+}
+
+#[derive(Deserialize)]
+struct ToCrsKwargs {
+    crs: String,
+    source_crs: Option<String>,
+}
+
+/// The CRS to convert from, the column's own CRS or `source_crs` for a column
+/// without one.
+fn source_crs(dtype: &DataType, kwargs: &ToCrsKwargs) -> PolarsResult<String> {
+    match (dtype_crs(dtype)?, kwargs.source_crs.as_deref()) {
+        (Some(crs), Some(source)) => {
+            polars_ensure!(
+                crs == source,
+                ComputeError: "source_crs {} doesn't match the column's CRS {}, use set_crs to override it",
+                source, crs
+            );
+            Ok(source.to_string())
+        }
+        (Some(crs), None) => Ok(crs.to_string()),
+        (None, Some(source)) => Ok(source.to_string()),
+        (None, None) => polars_bail!(
+            ComputeError: "geometry column has no CRS, pass source_crs or use set_crs first"
+        ),
+    }
+}
+
+fn to_crs_output(fields: &[Field], kwargs: ToCrsKwargs) -> PolarsResult<Field> {
+    source_crs(fields[0].dtype(), &kwargs)?;
+    FieldsMapper::new(fields).with_dtype(geometry_dtype(Some(&kwargs.crs)))
+}
+
+#[polars_expr(output_type_func_with_kwargs=to_crs_output)]
+fn to_crs(inputs: &[Series], kwargs: ToCrsKwargs) -> PolarsResult<Series> {
+    let source = source_crs(inputs[0].dtype(), &kwargs)?;
+    let reprojection = Reprojection::new(&source, &kwargs.crs)?;
+    let out = run_geometry_op_on_struct(inputs, |g| reprojection.apply(g))?;
+    rename_crs(&out, Some(&kwargs.crs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test_utils::{geometry_series, read_geometries};
+    use geo::{Geometry, LineString, Rect, Triangle, line_string, point, polygon};
+
+    type Predicate = fn(&Geometry, &Geometry) -> bool;
+
+    fn square() -> Series {
+        let square = polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)];
+        geometry_series(None, &[Some(square.into())])
+    }
+
+    /// A point inside the square, a point on its edge, a line across it, a
+    /// polygon overlapping it, a point away from it and a null row.
+    fn rows() -> Series {
+        geometry_series(None, &[
+            Some(point!(x: 1., y: 1.).into()),
+            Some(point!(x: 4., y: 2.).into()),
+            Some(line_string![(x: -1., y: 2.), (x: 5., y: 2.)].into()),
+            Some(polygon![(x: 2., y: 2.), (x: 6., y: 2.), (x: 6., y: 6.), (x: 2., y: 6.)].into()),
+            Some(point!(x: 9., y: 9.).into()),
+            None,
+        ])
+    }
+
+    fn check(inputs: [Series; 2], predicate: Predicate, expected: [bool; 5]) {
+        let out = run_binary_op_on_structs(&inputs, &DataType::Boolean, predicate).unwrap();
+        let mut expected: Vec<Option<bool>> = expected.into_iter().map(Some).collect();
+        expected.push(None);
+        assert_eq!(out.bool().unwrap().into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn predicates_of_rows_against_a_square() {
+        let cases: [(Predicate, [bool; 5]); 6] = [
+            (|g, o| g.intersects(o), [true, true, true, true, false]),
+            (|g, o| !g.intersects(o), [false, false, false, false, true]),
+            (|g, o| g.is_within(o), [true, false, false, false, false]),
+            (|g, o| g.relate(o).is_touches(), [false, true, false, false, false]),
+            (|g, o| g.relate(o).is_crosses(), [false, false, true, false, false]),
+            (|g, o| g.relate(o).is_overlaps(), [false, false, false, true, false]),
+        ];
+        for (predicate, expected) in cases {
+            check([rows(), square()], predicate, expected);
+        }
+    }
+
+    #[test]
+    fn predicates_of_a_square_against_rows() {
+        let cases: [(Predicate, [bool; 5]); 2] = [
+            (|g, o| g.contains(o), [true, false, false, false, false]),
+            (|g, o| g.relate(o).is_covers(), [true, true, false, false, false]),
+        ];
+        for (predicate, expected) in cases {
+            check([square(), rows()], predicate, expected);
+        }
+    }
+
+    #[test]
+    fn relate_gives_the_de9im_matrix() {
+        let out = run_binary_op_on_structs(&[rows(), square()], &DataType::String, |g, o| de9im_string(&g.relate(o)))
+            .unwrap();
+        assert_eq!(
+            out.str().unwrap().into_iter().collect::<Vec<_>>(),
+            [
+                Some("0FFFFF212"),
+                Some("F0FFFF212"),
+                Some("101FF0212"),
+                Some("212101212"),
+                Some("FF0FFF212"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn relate_pattern_matches_wildcards() {
+        let within = "T*F**F***";
+        let out = run_binary_op_on_structs(&[rows(), square()], &DataType::Boolean, |g, o| {
+            g.relate(o).matches(within).ok()
+        })
+        .unwrap();
+        assert_eq!(
+            out.bool().unwrap().into_iter().collect::<Vec<_>>(),
+            [Some(true), Some(false), Some(false), Some(false), Some(false), None]
+        );
+        assert!(IntersectionMatrix::empty().matches("T*F**F**").is_err());
+    }
+
+    fn convex(g: &Geometry) -> Option<Polygon> {
+        (g.coords_count() > 0).then(|| g.convex_hull())
+    }
+
+    /// Group a is a right triangle with a point inside it and a null row,
+    /// group b is a smaller triangle.
+    fn groups() -> DataFrame {
+        let geoms = [
+            Some(point!(x: 0., y: 0.).into()),
+            Some(point!(x: 10., y: 10.).into()),
+            Some(line_string![(x: 2., y: 0.), (x: 0., y: 2.)].into()),
+            Some(point!(x: 0.5, y: 0.5).into()),
+            None,
+            Some(line_string![(x: 11., y: 10.), (x: 10., y: 11.)].into()),
+        ];
+        DataFrame::new(vec![
+            Column::new("group".into(), ["a", "b", "a", "a", "a", "b"]),
+            geometry_series(Some("EPSG:3857"), &geoms).into_column(),
+        ])
+        .unwrap()
+    }
+
+    fn areas(s: &Series) -> Vec<Option<f64>> {
+        read_geometries(s).iter().map(|g| g.as_ref().map(|g| g.unsigned_area())).collect()
+    }
+
+    #[test]
+    fn hulls_aggregate_each_group() {
+        let hull = |c: Column| {
+            let s = run_hull(&[c.as_materialized_series().clone()], true, convex)?;
+            Ok(Some(s.into_column()))
+        };
+        let out = groups()
+            .lazy()
+            .group_by_stable([col("group")])
+            .agg([col("GEOMETRY").apply(hull, GetOutput::same_type()).first()])
+            .collect()
+            .unwrap();
+        let hulls = out.column("GEOMETRY").unwrap().as_materialized_series();
+        assert_eq!(hulls.dtype(), &geometry_dtype(Some("EPSG:3857")));
+        assert_eq!(areas(hulls), [Some(2.), Some(0.5)]);
+
+        let rects = run_hull(&[groups().column("GEOMETRY").unwrap().as_materialized_series().clone()], true, |g| {
+            g.minimum_rotated_rect()
+        })
+        .unwrap();
+        assert_eq!(rects.len(), 1);
+        assert!(areas(&rects)[0].unwrap() > 2.);
+    }
+
+    #[test]
+    fn hulls_per_row_keep_the_rows() {
+        let s = groups().column("GEOMETRY").unwrap().as_materialized_series().clone();
+        let hulls = run_hull(&[s], false, convex).unwrap();
+        assert_eq!(areas(&hulls), [Some(0.), Some(0.), Some(0.), Some(0.), None, Some(0.)]);
+    }
+
+    #[test]
+    fn geodesic_buffers_need_a_geographic_crs() {
+        let rows = |crs| geometry_series(crs, &[Some(point!(x: 1., y: 1.).into())]);
+        assert!(check_geodesic_crs(rows(Some("EPSG:4326")).dtype()).is_ok());
+        assert!(check_geodesic_crs(rows(Some("EPSG:3857")).dtype()).is_err());
+        assert!(check_geodesic_crs(rows(None).dtype()).is_err());
+    }
+
+    fn fields(inputs: &[Series]) -> Vec<Field> {
+        inputs.iter().map(|s| s.field().into_owned()).collect()
+    }
+
+    fn lines() -> Series {
+        let line: Geometry = line_string![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.)].into();
+        geometry_series(Some("EPSG:3857"), &[Some(line.clone()), None, Some(line)])
+    }
+
+    #[test]
+    fn line_interpolate_point_with_scalar_and_column() {
+        let scalar = Series::new("fraction".into(), [0.75]);
+        let column = Series::new("fraction".into(), [Some(0.25), Some(0.5), None]);
+        for fraction in [scalar, column] {
+            let inputs = [lines(), fraction.clone()];
+            let field = geometry_with_values_output(&fields(&inputs)).unwrap();
+            let out = run_geometry_op_with_values_on_struct(&inputs, |g, values| {
+                interpolate_point(g, values[0], true)
+            })
+            .unwrap();
+            assert_eq!(out.dtype(), field.dtype());
+            let expected = match fraction.len() {
+                1 => vec![Some(point!(x: 10., y: 5.).into()), None, Some(point!(x: 10., y: 5.).into())],
+                _ => vec![Some(point!(x: 5., y: 0.).into()), None, None],
+            };
+            assert_eq!(read_geometries(&out), expected);
+        }
+    }
+
+    #[test]
+    fn line_substring_with_scalar_and_column() {
+        let start = Series::new("start".into(), [0.25]);
+        let end = Series::new("end".into(), [Some(0.75), Some(0.75), Some(0.0)]);
+        let inputs = [lines(), start, end];
+        let field = geometry_with_values_output(&fields(&inputs)).unwrap();
+        let out = run_geometry_op_with_values_on_struct(&inputs, |g, values| {
+            substring(g, values[0], values[1], true)
+        })
+        .unwrap();
+        assert_eq!(out.dtype(), field.dtype());
+        let forward: LineString = line_string![(x: 5., y: 0.), (x: 10., y: 0.), (x: 10., y: 5.)];
+        let backward: LineString = line_string![(x: 5., y: 0.), (x: 0., y: 0.)];
+        assert_eq!(
+            read_geometries(&out),
+            vec![Some(forward.into()), None, Some(backward.into())]
+        );
+    }
+
+    #[test]
+    fn overlay_of_rect_and_triangle() {
+        let rect: Geometry = Rect::new((0., 0.), (2., 2.)).into();
+        let triangle: Geometry = Triangle::from([(1., 0.), (3., 0.), (1., 2.)]).into();
+        let square: Geometry = polygon![(x: 1., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 1., y: 2.)].into();
+
+        let rect_area = overlay(&rect, &square, OpType::Intersection).unwrap().unsigned_area();
+        assert!((rect_area - 2.0).abs() < 1e-9);
+        let both = overlay(&rect, &triangle, OpType::Intersection).unwrap().unsigned_area();
+        assert!((both - 1.5).abs() < 1e-9);
+        let union = overlay(&triangle, &rect, OpType::Union).unwrap().unsigned_area();
+        assert!((union - 4.5).abs() < 1e-9);
+        assert!(overlay(&rect, &point!(x: 1., y: 1.).into(), OpType::Union).is_none());
+    }
+}
@@ -1,4 +1,3 @@
 mod builders;
-mod enums;
 mod parse_fn;
 pub use parse_fn::read_kml;
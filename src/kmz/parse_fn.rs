@@ -1,22 +1,17 @@
 use crate::kmz::builders::Builders;
-use crate::kmz::enums::*;
 use ::zip::read::ZipArchive;
+use geo::{GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
 use kml::Kml;
-use kml::types::{Coord, Geometry, LineString, LinearRing, Placemark, Point, Polygon};
-use polars::chunked_array::builder::AnonymousListBuilder;
+use kml::types::{Geometry, Placemark};
 use polars::prelude::*;
 use std::fs::File;
 use std::io::Read;
 
-pub(crate) fn parse_point(builders: &mut Builders, point: Point) {
-    builders.add_point(point, true);
-}
-pub(crate) fn parse_multigeometry(builders: &mut Builders, geoms: Vec<Geometry>, add_row: bool) {
+pub(crate) fn parse_multigeometry(geoms: Vec<Geometry>) -> PolarsResult<Option<geo::Geometry>> {
     // separate the list of geometries into lists of each kind of geometry, first create empty vecs
     let mut geoms = geoms;
     let mut points: Vec<Point> = vec![];
-    let mut line_strings: Vec<LineString> = vec![];
-    let mut linear_rings: Vec<LinearRing> = vec![];
+    let mut lines: Vec<LineString> = vec![];
     let mut polygons: Vec<Polygon> = vec![];
 
     // if nested multi, need to flatten
@@ -30,161 +25,171 @@ pub(crate) fn parse_multigeometry(builders: &mut Builders, geoms: Vec<Geometry>,
             i += 1;
         }
     }
-    if geoms.len() == 0 {
-        builders.row += 1;
-        return;
-    } else if geoms.len() <= 1 {
-        parse_geometry(builders, geoms.remove(0), add_row);
-        return;
+    if geoms.len() <= 1 {
+        return Ok(geoms.pop().map(parse_geometry).transpose()?.flatten());
     }
-    // move each geo type to its vec
-    geoms.into_iter().for_each(|geom| match geom {
-        Geometry::LineString(ls) => line_strings.push(ls),
-        Geometry::LinearRing(lr) => linear_rings.push(lr),
-        Geometry::Point(p) => points.push(p),
-        Geometry::Polygon(poly) => polygons.push(poly),
-        _ => {
-            eprintln!("found multi with unsupported {:?}", geom);
+    // move each geo type to its vec, a LinearRing is just a closed LineString
+    for geom in geoms {
+        match geom {
+            Geometry::LineString(ls) => lines.push(ls.into()),
+            Geometry::LinearRing(lr) => lines.push(lr.into()),
+            Geometry::Point(p) => points.push(p.into()),
+            Geometry::Polygon(poly) => polygons.push(poly.into()),
+            other => polars_bail!(
+                ComputeError: "MultiGeometry holds an unsupported KML geometry: {:?}", other
+            ),
         }
-    });
+    }
     // for each type, if only one treat it as that type, if multiple, treat it as MULTI
+    let mut parts: Vec<geo::Geometry> = vec![];
     match points.len() {
         0 => {}
-        1 => builders.add_point(points.remove(0), false),
-        _ => builders.add_points(points, false),
+        1 => parts.push(points.remove(0).into()),
+        _ => parts.push(MultiPoint(points).into()),
     };
-    match line_strings.len() {
-        0 => {}
-        1 => builders.add_line(line_strings.remove(0).coords, LineKind::LineString, false),
-        _ => {
-            let ls_coords: Vec<Vec<Coord>> = line_strings.into_iter().map(|ls| ls.coords).collect();
-            builders.add_lines(ls_coords, MultiLineKind::MultiLineString, false);
-        }
-    }
-    match linear_rings.len() {
+    match lines.len() {
         0 => {}
-        1 => builders.add_line(linear_rings.remove(0).coords, LineKind::LineString, false),
-        _ => {
-            let lr_coords: Vec<Vec<Coord>> = linear_rings.into_iter().map(|lr| lr.coords).collect();
-            builders.add_lines(lr_coords, MultiLineKind::MultiLinearRing, false);
-        }
+        1 => parts.push(lines.remove(0).into()),
+        _ => parts.push(MultiLineString(lines).into()),
     };
     match polygons.len() {
         0 => {}
-        1 => builders.add_polygon(polygons.remove(0), false),
-        _ => builders.add_polygons(polygons, false),
-    }
-    // This is implicitly assuming that at least one type existed but it isn't checked.
-
-    builders.row += 1;
+        1 => parts.push(polygons.remove(0).into()),
+        _ => parts.push(MultiPolygon(polygons).into()),
+    };
+    // a mix of kinds is read back as a GEOMETRYCOLLECTION anyway
+    Ok(match parts.len() {
+        0 => None,
+        1 => parts.pop(),
+        _ => Some(geo::Geometry::GeometryCollection(GeometryCollection(parts))),
+    })
 }
-pub(crate) fn parse_geometry(builders: &mut Builders, geometry: Geometry, add_row: bool) {
-    match geometry {
-        Geometry::Point(point) => builders.add_point(point, add_row),
-        Geometry::Element(_) => {}
-        Geometry::LineString(ls) => builders.add_line(ls.coords, LineKind::LineString, add_row),
-        Geometry::LinearRing(ls) => builders.add_line(ls.coords, LineKind::LinearRing, add_row),
-        Geometry::Polygon(poly) => builders.add_polygon(poly, add_row),
-        Geometry::MultiGeometry(multi_geom) => {
-            parse_multigeometry(builders, multi_geom.geometries, add_row)
-        }
-        _ => {}
-    }
+pub(crate) fn parse_geometry(geometry: Geometry) -> PolarsResult<Option<geo::Geometry>> {
+    Ok(match geometry {
+        Geometry::Point(point) => Some(Point::from(point).into()),
+        Geometry::LineString(ls) => Some(LineString::from(ls).into()),
+        Geometry::LinearRing(lr) => Some(LineString::from(lr).into()),
+        Geometry::Polygon(poly) => Some(Polygon::from(poly).into()),
+        Geometry::MultiGeometry(multi_geom) => parse_multigeometry(multi_geom.geometries)?,
+        _ => None,
+    })
 }
-pub(crate) fn parse_placemark(builders: &mut Builders, placemark: Placemark) {
-    match placemark.geometry {
-        Some(geometry) => parse_geometry(builders, geometry, true),
-        None => builders.row += 1,
-    };
-    builders.add_name(placemark.name.as_deref());
-    builders.add_description(placemark.description.as_deref());
+pub(crate) fn parse_placemark(builders: &mut Builders, placemark: Placemark) -> PolarsResult<()> {
+    builders.add_row(
+        placemark.geometry.map(parse_geometry).transpose()?.flatten(),
+        placemark.name.as_deref(),
+        placemark.description.as_deref(),
+    )
 }
-pub(crate) fn iter_elems(builders: &mut Builders, elems: Vec<Kml>) {
+pub(crate) fn iter_elems(builders: &mut Builders, elems: Vec<Kml>) -> PolarsResult<()> {
     elems
         .into_iter()
-        .for_each(|kml| parse_kml_inner(builders, kml))
+        .try_for_each(|kml| parse_kml_inner(builders, kml))
 }
-pub(crate) fn parse_kml_inner(builders: &mut Builders, kml: Kml) {
+pub(crate) fn parse_kml_inner(builders: &mut Builders, kml: Kml) -> PolarsResult<()> {
     match kml {
         Kml::KmlDocument(doc) => iter_elems(builders, doc.elements),
-        Kml::Point(point) => parse_point(builders, point),
+        Kml::Point(point) => builders.add_row(Some(Point::from(point).into()), None, None),
         Kml::Placemark(placemark) => parse_placemark(builders, placemark),
         Kml::Document { attrs: _, elements } => iter_elems(builders, elements),
-        Kml::Folder { attrs: _, elements } => {
-            iter_elems(builders, elements);
-        }
-        Kml::Style(_) => {}
-        _ => {}
+        Kml::Folder { attrs: _, elements } => iter_elems(builders, elements),
+        Kml::Style(_) => Ok(()),
+        _ => Ok(()),
     }
 }
-pub(crate) fn parse_kml(kml: Kml) -> DataFrame {
+pub(crate) fn parse_kml(kml: Kml) -> PolarsResult<DataFrame> {
     let mut builders = Builders::new();
-    parse_kml_inner(&mut builders, kml);
+    parse_kml_inner(&mut builders, kml)?;
     builders.finish_geometry()
 }
-pub fn read_kml(kml_path: String, sink_path: Option<String>) -> DataFrame {
-    let file = File::open(kml_path).unwrap();
-    let mut archive = ZipArchive::new(file).unwrap();
+pub fn read_kml(kml_path: String, sink_path: Option<String>) -> PolarsResult<DataFrame> {
+    let file = File::open(&kml_path)
+        .map_err(|e| polars_err!(ComputeError: "can't open {}: {}", kml_path, e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| polars_err!(ComputeError: "{} is not a valid kmz archive: {}", kml_path, e))?;
     for i in 0..archive.len() {
-        let mut inner_file = archive.by_index(i).unwrap();
+        let mut inner_file = archive
+            .by_index(i)
+            .map_err(|e| polars_err!(ComputeError: "can't read entry {} of {}: {}", i, kml_path, e))?;
         let file_name = inner_file.name().to_string();
 
         if file_name.ends_with(".kml") {
             let mut contents = String::new();
-            inner_file.read_to_string(&mut contents).unwrap();
+            inner_file
+                .read_to_string(&mut contents)
+                .map_err(|e| polars_err!(ComputeError: "can't read {}: {}", file_name, e))?;
             let lines = contents.lines().collect::<Vec<_>>();
-            let kml_string = if lines[0].starts_with("<?xml") {
-                lines[1..lines.len()].join("\n")
-            } else {
-                lines.join("\n")
+            let kml_string = match lines.first() {
+                Some(first) if first.starts_with("<?xml") => lines[1..].join("\n"),
+                Some(_) => lines.join("\n"),
+                None => polars_bail!(ComputeError: "{} is empty", file_name),
             };
-            let kml_data: Kml = kml_string.parse().unwrap();
-            let df = parse_kml(kml_data);
+            let kml_data: Kml = kml_string
+                .parse()
+                .map_err(|e| polars_err!(ComputeError: "can't parse {}: {}", file_name, e))?;
+            let mut df = parse_kml(kml_data)?;
             if let Some(save_path) = sink_path {
-                let file_save = File::create(save_path).unwrap();
-                ParquetWriter::new(file_save)
-                    .finish(&mut df.clone())
-                    .unwrap();
+                let file_save = File::create(&save_path)
+                    .map_err(|e| polars_err!(ComputeError: "can't create {}: {}", save_path, e))?;
+                ParquetWriter::new(file_save).finish(&mut df)?;
             }
-            return df;
+            return Ok(df);
         }
     }
-    DataFrame::empty()
+    Ok(DataFrame::empty())
 }
 
-pub(crate) fn poly_to_series(polygon: Polygon) -> Series {
-    let outer_size = polygon.outer.coords.len();
-    let outer_coords_fs = coords_to_series(polygon.outer.coords);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::geometry_dtype;
+    use crate::ops::test_utils::read_geometries;
+    use geo::{line_string, point, polygon};
+    use kml::types::{Element, MultiGeometry};
 
-    let inner_size: usize = polygon.inner.iter().map(|lr| lr.coords.len()).sum();
-    let size = (outer_size + inner_size) * 2;
-    let mut ls_builder = AnonymousListBuilder::new(
-        "".into(),
-        size,
-        Some(DataType::Array(Box::new(DataType::Float64), 2)),
-    );
-    ls_builder.append_series(&outer_coords_fs).unwrap();
-    let inner_ss: Vec<Series> = polygon
-        .inner
-        .into_iter()
-        .map(|ls| coords_to_series(ls.coords))
-        .collect();
-    for s in inner_ss.iter() {
-        ls_builder.append_series(s).unwrap();
+    const KML: &str = r#"<kml xmlns="http://www.opengis.net/kml/2.2"><Document>
+<Placemark><name>a</name><Point><coordinates>1,2</coordinates></Point></Placemark>
+<Placemark><name>b</name><description>mixed</description><MultiGeometry>
+  <Point><coordinates>0,0</coordinates></Point>
+  <Polygon><outerBoundaryIs><LinearRing><coordinates>0,0 1,0 1,1 0,0</coordinates></LinearRing></outerBoundaryIs></Polygon>
+</MultiGeometry></Placemark>
+<Placemark><name>c</name></Placemark>
+<Folder><Placemark><LinearRing><coordinates>0,0 2,0 2,2 0,0</coordinates></LinearRing></Placemark></Folder>
+</Document></kml>"#;
+
+    #[test]
+    fn placemarks_become_rows_of_a_geometry_struct() {
+        let df = parse_kml(KML.parse().unwrap()).unwrap();
+        let geometry = df.column("GEOMETRY").unwrap().as_materialized_series();
+        assert_eq!(geometry.dtype(), &geometry_dtype(Some("EPSG:4326")));
+        let names = df.column("Name").unwrap().str().unwrap();
+        assert_eq!(Vec::from(names), vec![Some("a"), Some("b"), Some("c"), None]);
+
+        let triangle = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        assert_eq!(
+            read_geometries(geometry),
+            vec![
+                Some(point!(x: 1., y: 2.).into()),
+                Some(geo::Geometry::GeometryCollection(GeometryCollection(vec![
+                    point!(x: 0., y: 0.).into(),
+                    triangle.into(),
+                ]))),
+                None,
+                Some(line_string![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 0.)].into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_geometries_in_a_multigeometry_are_an_error() {
+        let multi = MultiGeometry::new(vec![
+            Geometry::Point(kml::types::Point::new(0., 0., None)),
+            Geometry::Element(Element::default()),
+        ]);
+        let placemark = Placemark {
+            geometry: Some(Geometry::MultiGeometry(multi)),
+            ..Default::default()
+        };
+        assert!(parse_kml(Kml::Placemark(placemark)).is_err());
     }
-    ls_builder.finish().into_series()
-}
-pub(crate) fn coords_to_series(coords: Vec<Coord>) -> Series {
-    let coords_av: Vec<AnyValue> = coords
-        .iter()
-        .map(|coord| AnyValue::List(Series::new("".into(), vec![coord.x, coord.y])))
-        .collect();
-    let coord_fs = Series::from_any_values_and_dtype(
-        "".into(),
-        &coords_av,
-        &DataType::Array(Box::new(DataType::Float64), 2),
-        true,
-    )
-    .unwrap();
-    coord_fs
 }
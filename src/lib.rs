@@ -2,8 +2,12 @@ mod kmz;
 mod ops;
 use kmz::read_kml;
 mod exprs;
+use geo::Rect;
+use ops::{JoinHow, Metric, SpatialIndex, SpatialPredicate};
+use polars::prelude::*;
 use pyo3::prelude::*;
-use pyo3_polars::{PolarsAllocator, PyDataFrame};
+use pyo3_polars::error::PyPolarsErr;
+use pyo3_polars::{PolarsAllocator, PyDataFrame, PySeries};
 
 #[global_allocator]
 static ALLOC: PolarsAllocator = PolarsAllocator::new();
@@ -11,14 +15,110 @@ static ALLOC: PolarsAllocator = PolarsAllocator::new();
 #[pyfunction]
 #[pyo3(signature=(path))]
 fn read_kmz(path: &str) -> PyResult<PyDataFrame> {
-    let df = read_kml(path.to_string(), None);
+    let df = read_kml(path.to_string(), None).map_err(PyPolarsErr::from)?;
     Ok(PyDataFrame(df))
 }
 
+#[pyfunction]
+#[pyo3(signature=(left, right, predicate, how, left_on, right_on, suffix))]
+fn sjoin(
+    left: PyDataFrame,
+    right: PyDataFrame,
+    predicate: &str,
+    how: &str,
+    left_on: &str,
+    right_on: &str,
+    suffix: &str,
+) -> PyResult<PyDataFrame> {
+    let df = SpatialPredicate::parse(predicate)
+        .and_then(|predicate| Ok((predicate, JoinHow::parse(how)?)))
+        .and_then(|(predicate, how)| {
+            ops::sjoin(&left.0, &right.0, left_on, right_on, predicate, how, suffix)
+        })
+        .map_err(PyPolarsErr::from)?;
+    Ok(PyDataFrame(df))
+}
+
+#[pyfunction]
+#[pyo3(signature=(left, right, k, max_distance, distance_col, metric, how, left_on, right_on, suffix))]
+#[allow(clippy::too_many_arguments)]
+fn sjoin_nearest(
+    left: PyDataFrame,
+    right: PyDataFrame,
+    k: usize,
+    max_distance: Option<f64>,
+    distance_col: Option<&str>,
+    metric: &str,
+    how: &str,
+    left_on: &str,
+    right_on: &str,
+    suffix: &str,
+) -> PyResult<PyDataFrame> {
+    let df = Metric::parse(metric)
+        .and_then(|metric| Ok((metric, JoinHow::parse(how)?)))
+        .and_then(|(metric, how)| {
+            ops::sjoin_nearest(
+                &left.0,
+                &right.0,
+                left_on,
+                right_on,
+                k,
+                max_distance,
+                distance_col,
+                metric,
+                how,
+                suffix,
+            )
+        })
+        .map_err(PyPolarsErr::from)?;
+    Ok(PyDataFrame(df))
+}
+
+/// Python handle on a `SpatialIndex`, built once from a GEOMETRY Series.
+#[pyclass(name = "SpatialIndex", frozen)]
+struct PySpatialIndex(SpatialIndex);
+
+#[pymethods]
+impl PySpatialIndex {
+    #[new]
+    #[pyo3(signature=(geometries))]
+    fn new(geometries: PySeries) -> PyResult<Self> {
+        let index = SpatialIndex::new(&geometries.0).map_err(PyPolarsErr::from)?;
+        Ok(PySpatialIndex(index))
+    }
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+    #[getter]
+    fn crs(&self) -> Option<String> {
+        self.0.crs().map(|crs| crs.to_string())
+    }
+    #[pyo3(signature=(geometries))]
+    fn query(&self, geometries: PySeries) -> PyResult<PyDataFrame> {
+        let df = self.0.query(&geometries.0).map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+    #[pyo3(signature=(minx, miny, maxx, maxy))]
+    fn query_envelope(&self, minx: f64, miny: f64, maxx: f64, maxy: f64) -> PySeries {
+        let rows = self
+            .0
+            .query_envelope(Rect::new((minx, miny), (maxx, maxy)));
+        PySeries(IdxCa::from_vec("tree_index".into(), rows).into_series())
+    }
+    #[pyo3(signature=(x, y, k=1))]
+    fn nearest(&self, x: f64, y: f64, k: usize) -> PySeries {
+        let rows = self.0.nearest(x, y, k);
+        PySeries(IdxCa::from_vec("tree_index".into(), rows).into_series())
+    }
+}
+
 #[pymodule]
 #[pyo3(name = "_geopl")]
 fn _geopl(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(read_kmz, m)?)?;
+    m.add_function(wrap_pyfunction!(sjoin, m)?)?;
+    m.add_function(wrap_pyfunction!(sjoin_nearest, m)?)?;
+    m.add_class::<PySpatialIndex>()?;
     Ok(())
 }
 
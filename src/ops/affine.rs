@@ -0,0 +1,115 @@
+use geo::{AffineOps, AffineTransform, BoundingRect, Centroid, Coord, Geometry};
+use polars::prelude::*;
+
+/// The fixed point of a rotation, scale or skew.
+#[derive(Clone, Copy)]
+pub enum Origin {
+    /// The centroid of each geometry.
+    Centroid,
+    /// The centre of the bounding box of each geometry.
+    Center,
+    /// The same coordinate for every geometry.
+    Point(Coord),
+}
+impl Origin {
+    pub fn parse(name: &str, point: Option<[f64; 2]>) -> PolarsResult<Origin> {
+        match (name, point) {
+            ("centroid", _) => Ok(Origin::Centroid),
+            ("center", _) => Ok(Origin::Center),
+            ("point", Some([x, y])) => Ok(Origin::Point(Coord { x, y })),
+            _ => polars_bail!(
+                InvalidOperation: "unknown origin {}, expected centroid, center or an (x, y) coordinate", name
+            ),
+        }
+    }
+    /// `None` for empty geometries, which have neither a centroid nor a bounding box.
+    fn coord(self, geom: &Geometry) -> Option<Coord> {
+        match self {
+            Origin::Centroid => geom.centroid().map(|p| p.0),
+            Origin::Center => geom.bounding_rect().map(|r| r.center()),
+            Origin::Point(c) => Some(c),
+        }
+    }
+}
+
+/// Transforms `geom`, keeping the geometry type. A RECT that gets rotated or
+/// skewed is no longer axis aligned so it becomes a POLYGON.
+pub(crate) fn apply_affine(geom: &Geometry, transform: &AffineTransform) -> Geometry {
+    match geom {
+        Geometry::Rect(r) if transform.b() != 0.0 || transform.d() != 0.0 => {
+            r.to_polygon().affine_transform(transform).into()
+        }
+        _ => geom.affine_transform(transform),
+    }
+}
+
+/// Transforms `geom` with the transform `make` builds around its origin.
+/// Empty geometries come back unchanged.
+pub(crate) fn transform_around<F>(geom: &Geometry, origin: Origin, make: F) -> Geometry
+where
+    F: Fn(Coord) -> AffineTransform,
+{
+    match origin.coord(geom) {
+        Some(origin) => apply_affine(geom, &make(origin)),
+        None => geom.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{CoordsIter, LineString, Rect, Triangle, polygon};
+
+    fn assert_coords(geom: &Geometry, expected: &[(f64, f64)]) {
+        let coords: Vec<Coord> = geom.coords_iter().collect();
+        assert_eq!(coords.len(), expected.len(), "{:?}", coords);
+        for (c, (x, y)) in coords.iter().zip(expected) {
+            assert!((c.x - x).abs() < 1e-9 && (c.y - y).abs() < 1e-9, "{:?}", coords);
+        }
+    }
+
+    #[test]
+    fn rotate_about_the_centroid() {
+        // a 4x2 rectangle with its centroid at (2, 1)
+        let rect: Geometry = polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 2.), (x: 0., y: 2.)].into();
+        let rotated = transform_around(&rect, Origin::Centroid, |o| AffineTransform::rotate(90., o));
+        assert_coords(&rotated, &[(3., -1.), (3., 3.), (1., 3.), (1., -1.), (3., -1.)]);
+        assert_eq!(rotated.centroid(), rect.centroid());
+    }
+
+    #[test]
+    fn scale_about_the_centroid_or_the_center() {
+        // the centroid is at (1, 1), the bounding box center at (1.5, 1.5)
+        let triangle: Geometry = polygon![(x: 0., y: 0.), (x: 3., y: 0.), (x: 0., y: 3.)].into();
+        let scale = |origin| transform_around(&triangle, origin, |o| AffineTransform::scale(2., 2., o));
+        assert_coords(&scale(Origin::Centroid), &[(-1., -1.), (5., -1.), (-1., 5.), (-1., -1.)]);
+        assert_coords(
+            &scale(Origin::Center),
+            &[(-1.5, -1.5), (4.5, -1.5), (-1.5, 4.5), (-1.5, -1.5)],
+        );
+        assert_coords(
+            &scale(Origin::parse("point", Some([0., 0.])).unwrap()),
+            &[(0., 0.), (6., 0.), (0., 6.), (0., 0.)],
+        );
+    }
+
+    #[test]
+    fn rects_stay_rects_unless_rotated_or_skewed() {
+        let rect: Geometry = Rect::new((0., 0.), (2., 2.)).into();
+        let scaled = transform_around(&rect, Origin::Center, |o| AffineTransform::scale(2., 1., o));
+        assert_eq!(scaled, Rect::new((-1., 0.), (3., 2.)).into());
+        let rotated = transform_around(&rect, Origin::Center, |o| AffineTransform::rotate(45., o));
+        assert!(matches!(rotated, Geometry::Polygon(_)));
+        let skewed = apply_affine(&Triangle::from([(0., 0.), (1., 0.), (0., 1.)]).into(), &AffineTransform::skew(10., 0., Coord::zero()));
+        assert!(matches!(skewed, Geometry::Triangle(_)));
+    }
+
+    #[test]
+    fn empty_geometries_have_no_origin() {
+        let empty: Geometry = LineString::new(vec![]).into();
+        let out = transform_around(&empty, Origin::Centroid, |o| AffineTransform::rotate(90., o));
+        assert_eq!(out, empty);
+        assert!(Origin::parse("point", None).is_err());
+        assert!(Origin::parse("corner", None).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use geo::Rect;
+use polars::prelude::*;
+use polars_arrow::bitmap::Bitmap;
+use polars_core::POOL;
+use rayon::prelude::*;
+
+use super::to_geom::Geos;
+use super::to_pl::Builder;
+
+const BOUNDS_FIELDS: [&str; 4] = ["minx", "miny", "maxx", "maxy"];
+
+/// The bounding rectangle of every row, read straight from the coordinate
+/// buffers. Null and empty geometries have none.
+pub(crate) fn bounding_rects(geometries: &Geos, rows: usize) -> PolarsResult<Vec<Option<Rect>>> {
+    POOL.install(|| {
+        (0..rows)
+            .into_par_iter()
+            .map(|i| geometries.bounds(i))
+            .collect()
+    })
+}
+
+/// A struct of `minx`, `miny`, `maxx` and `maxy` Float64 fields.
+pub fn bounds_dtype() -> DataType {
+    DataType::Struct(
+        BOUNDS_FIELDS
+            .iter()
+            .map(|name| Field::new((*name).into(), DataType::Float64))
+            .collect(),
+    )
+}
+
+fn bounds_struct(rects: &[Option<Rect>]) -> PolarsResult<Series> {
+    let corners: [fn(&Rect) -> f64; 4] = [
+        |r| r.min().x,
+        |r| r.min().y,
+        |r| r.max().x,
+        |r| r.max().y,
+    ];
+    let fields: Vec<Series> = BOUNDS_FIELDS
+        .iter()
+        .zip(corners)
+        .map(|(name, corner)| {
+            Float64Chunked::from_iter_options((*name).into(), rects.iter().map(|r| r.as_ref().map(corner)))
+                .into_series()
+        })
+        .collect();
+    let validity = rects
+        .iter()
+        .any(Option::is_none)
+        .then(|| Bitmap::from_iter(rects.iter().map(Option::is_some)));
+    Ok(
+        StructChunked::from_series(PlSmallStr::EMPTY, rects.len(), fields.iter())?
+            .with_outer_validity(validity)
+            .into_series(),
+    )
+}
+
+/// The bounds of every row, null for null and empty geometries.
+pub fn bounds(s: &Series) -> PolarsResult<Series> {
+    bounds_struct(&bounding_rects(&Geos::new(s)?, s.len())?)
+}
+
+/// The bounds of the whole column as a single row.
+pub fn total_bounds(s: &Series) -> PolarsResult<Series> {
+    let total = bounding_rects(&Geos::new(s)?, s.len())?
+        .into_iter()
+        .flatten()
+        .reduce(|a, b| {
+            Rect::new(
+                (a.min().x.min(b.min().x), a.min().y.min(b.min().y)),
+                (a.max().x.max(b.max().x), a.max().y.max(b.max().y)),
+            )
+        });
+    bounds_struct(&[total])
+}
+
+/// The bounding box of every row as a RECT, or a POLYGON with `as_polygon`.
+pub fn envelope(s: &Series, as_polygon: bool) -> PolarsResult<Series> {
+    let geometries = Geos::new(s)?;
+    let mut builder = Builder::new_geometry(s.len(), geometries.crs().map(|crs| crs.as_str()));
+    for rect in bounding_rects(&geometries, s.len())? {
+        match rect {
+            Some(rect) if as_polygon => builder.add(rect.to_polygon().into())?,
+            Some(rect) => builder.add(rect.into())?,
+            None => builder.add_null(),
+        }
+    }
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test_utils::{geometry_series, read_geometries};
+    use geo::{Geometry, LineString, Triangle, line_string, point, polygon};
+
+    fn column() -> Series {
+        let geoms: [Option<Geometry>; 5] = [
+            Some(point!(x: 1., y: 2.).into()),
+            None,
+            Some(line_string![(x: -1., y: 0.), (x: 3., y: 5.)].into()),
+            Some(LineString::<f64>::new(vec![]).into()),
+            Some(Triangle::from([(0., -2.), (2., 0.), (0., 1.)]).into()),
+        ];
+        geometry_series(Some("EPSG:3857"), &geoms)
+    }
+
+    fn corners(s: &Series) -> Vec<Option<[f64; 4]>> {
+        let ca = s.struct_().unwrap();
+        let fields = ca.fields_as_series();
+        let values: Vec<&Float64Chunked> = fields.iter().map(|f| f.f64().unwrap()).collect();
+        let valid = s.is_not_null();
+        (0..s.len())
+            .map(|i| {
+                valid
+                    .get(i)
+                    .unwrap()
+                    .then(|| [0, 1, 2, 3].map(|j| values[j].get(i).unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounds_of_every_row() {
+        let s = column();
+        let out = bounds(&s).unwrap();
+        assert_eq!(out.dtype(), &bounds_dtype());
+        assert_eq!(
+            corners(&out),
+            vec![
+                Some([1., 2., 1., 2.]),
+                None,
+                Some([-1., 0., 3., 5.]),
+                None,
+                Some([0., -2., 2., 1.]),
+            ]
+        );
+        assert_eq!(corners(&bounds(&s.slice(2, 3)).unwrap()), corners(&out)[2..].to_vec());
+    }
+
+    #[test]
+    fn total_bounds_of_the_column() {
+        let s = column();
+        let out = total_bounds(&s).unwrap();
+        assert_eq!(corners(&out), vec![Some([-1., -2., 3., 5.])]);
+        let nulls = geometry_series(None, &[None, None]);
+        assert_eq!(corners(&total_bounds(&nulls).unwrap()), vec![None]);
+    }
+
+    #[test]
+    fn envelope_as_rect_or_polygon() {
+        let s = column().slice(1, 2);
+        let rect = Rect::new((-1., 0.), (3., 5.));
+        let rects = envelope(&s, false).unwrap();
+        assert_eq!(rects.dtype(), s.dtype());
+        assert_eq!(read_geometries(&rects), vec![None, Some(rect.into())]);
+        let polygon = polygon![(x: -1., y: 0.), (x: 3., y: 0.), (x: 3., y: 5.), (x: -1., y: 5.)];
+        assert_eq!(
+            read_geometries(&envelope(&s, true).unwrap()),
+            vec![None, Some(polygon.into())]
+        );
+    }
+}
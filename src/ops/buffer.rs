@@ -0,0 +1,204 @@
+use geo::{
+    Bearing, BooleanOps, Centroid, Coord, Destination, Distance, Geodesic, Geometry, LineString,
+    MapCoords, MultiPolygon, Point, Polygon,
+};
+use std::f64::consts::TAU;
+
+/// Buffer built from BooleanOps: every vertex becomes a circle and every segment a
+/// rectangle, all unioned together. Polygons add (or, for negative distances,
+/// subtract) the buffer of their rings.
+pub(crate) trait Buffer {
+    fn buffer(&self, distance: f64, quad_segs: usize) -> MultiPolygon;
+}
+
+fn circle(center: Coord, radius: f64, quad_segs: usize) -> Polygon {
+    let n = quad_segs.max(1) * 4;
+    let coords: Vec<Coord> = (0..n)
+        .map(|i| {
+            let angle = TAU * i as f64 / n as f64;
+            Coord {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+            }
+        })
+        .collect();
+    Polygon::new(LineString::new(coords), vec![])
+}
+
+fn segment_rect(a: Coord, b: Coord, radius: f64) -> Option<Polygon> {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len = dx.hypot(dy);
+    if len == 0.0 {
+        return None;
+    }
+    let normal = Coord {
+        x: -dy / len * radius,
+        y: dx / len * radius,
+    };
+    Some(Polygon::new(
+        LineString::new(vec![a + normal, b + normal, b - normal, a - normal]),
+        vec![],
+    ))
+}
+
+/// Unions the pieces pairwise so each overlay works on similarly sized inputs.
+fn cascaded_union(mut pieces: Vec<MultiPolygon>) -> MultiPolygon {
+    while pieces.len() > 1 {
+        let mut merged = Vec::with_capacity(pieces.len() / 2 + 1);
+        let mut iter = pieces.into_iter();
+        while let Some(a) = iter.next() {
+            match iter.next() {
+                Some(b) => merged.push(a.union(&b)),
+                None => merged.push(a),
+            }
+        }
+        pieces = merged;
+    }
+    pieces.pop().unwrap_or_else(|| MultiPolygon::new(vec![]))
+}
+
+fn buffer_lines<'a>(
+    lines: impl Iterator<Item = &'a LineString>,
+    radius: f64,
+    quad_segs: usize,
+) -> MultiPolygon {
+    let mut pieces: Vec<MultiPolygon> = vec![];
+    lines.for_each(|line| {
+        line.0
+            .iter()
+            .for_each(|c| pieces.push(circle(*c, radius, quad_segs).into()));
+        line.0
+            .windows(2)
+            .filter_map(|w| segment_rect(w[0], w[1], radius))
+            .for_each(|rect| pieces.push(rect.into()));
+    });
+    cascaded_union(pieces)
+}
+
+fn buffer_polygon(polygon: &Polygon, distance: f64, quad_segs: usize) -> MultiPolygon {
+    if distance == 0.0 {
+        return polygon.clone().into();
+    }
+    let rings = std::iter::once(polygon.exterior()).chain(polygon.interiors());
+    let boundary = buffer_lines(rings, distance.abs(), quad_segs);
+    if distance > 0.0 {
+        polygon.union(&boundary)
+    } else {
+        polygon.difference(&boundary)
+    }
+}
+
+impl Buffer for Geometry {
+    fn buffer(&self, distance: f64, quad_segs: usize) -> MultiPolygon {
+        // points and lines have no area to shrink
+        if distance <= 0.0 && !matches!(
+            self,
+            Geometry::Polygon(_)
+                | Geometry::MultiPolygon(_)
+                | Geometry::Rect(_)
+                | Geometry::Triangle(_)
+                | Geometry::GeometryCollection(_)
+        ) {
+            return MultiPolygon::new(vec![]);
+        }
+        match self {
+            Geometry::Point(p) => circle(p.0, distance, quad_segs).into(),
+            Geometry::MultiPoint(mp) => cascaded_union(
+                mp.iter()
+                    .map(|p| circle(p.0, distance, quad_segs).into())
+                    .collect(),
+            ),
+            Geometry::Line(l) => buffer_lines(
+                std::iter::once(&LineString::new(vec![l.start, l.end])),
+                distance,
+                quad_segs,
+            ),
+            Geometry::LineString(ls) => buffer_lines(std::iter::once(ls), distance, quad_segs),
+            Geometry::MultiLineString(mls) => buffer_lines(mls.iter(), distance, quad_segs),
+            Geometry::Polygon(p) => buffer_polygon(p, distance, quad_segs),
+            Geometry::MultiPolygon(mp) => cascaded_union(
+                mp.iter()
+                    .map(|p| buffer_polygon(p, distance, quad_segs))
+                    .collect(),
+            ),
+            Geometry::Rect(r) => buffer_polygon(&r.to_polygon(), distance, quad_segs),
+            Geometry::Triangle(t) => buffer_polygon(&t.to_polygon(), distance, quad_segs),
+            Geometry::GeometryCollection(gc) => cascaded_union(
+                gc.iter().map(|g| g.buffer(distance, quad_segs)).collect(),
+            ),
+        }
+    }
+}
+
+/// Buffer lon/lat geometries by a distance in metres.
+///
+/// The geometry is projected to an azimuthal equidistant plane centred on its
+/// centroid, buffered there and projected back with geodesic destinations.
+pub(crate) fn geodesic_buffer(
+    geom: &Geometry,
+    distance: f64,
+    quad_segs: usize,
+) -> Option<MultiPolygon> {
+    let center = geom.centroid()?;
+    let local = geom.map_coords(|c| {
+        let p = Point::from(c);
+        let dist = Geodesic::distance(center, p);
+        let bearing = Geodesic::bearing(center, p).to_radians();
+        Coord {
+            x: dist * bearing.sin(),
+            y: dist * bearing.cos(),
+        }
+    });
+    let buffered = local.buffer(distance, quad_segs);
+    Some(buffered.map_coords(|c| {
+        let bearing = c.x.atan2(c.y).to_degrees();
+        Geodesic::destination(center, bearing, c.x.hypot(c.y)).0
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{Area, BoundingRect, CoordsIter, point, polygon};
+
+    fn square() -> Geometry {
+        polygon![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)].into()
+    }
+
+    #[test]
+    fn negative_buffer_shrinks_a_polygon() {
+        let shrunk = square().buffer(-1.0, 8);
+        assert!((shrunk.unsigned_area() - 64.0).abs() < 1e-6);
+        let rect = shrunk.bounding_rect().unwrap();
+        assert!((rect.min().x - 1.0).abs() < 1e-9 && (rect.max().y - 9.0).abs() < 1e-9);
+        assert!(square().buffer(-6.0, 8).0.is_empty());
+    }
+
+    #[test]
+    fn positive_buffer_rounds_the_corners() {
+        // the corners are quarters of a 32-gon of radius 1
+        let corners = 16.0 * (TAU / 32.0).sin();
+        let grown = square().buffer(1.0, 8);
+        assert!((grown.unsigned_area() - (100.0 + 40.0 + corners)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn points_and_lines_have_nothing_to_shrink() {
+        let p: Geometry = point!(x: 1., y: 1.).into();
+        assert!(p.buffer(-1.0, 8).0.is_empty());
+        assert!(p.buffer(0.0, 8).0.is_empty());
+        let circle = p.buffer(2.0, 8);
+        assert_eq!(circle.coords_count(), 33);
+    }
+
+    #[test]
+    fn geodesic_buffer_radius_is_in_metres() {
+        let center = point!(x: 10., y: 50.);
+        let buffered = geodesic_buffer(&center.into(), 1000.0, 8).unwrap();
+        assert_eq!(buffered.0.len(), 1);
+        for c in buffered.coords_iter() {
+            let d = Geodesic::distance(center, Point::from(c));
+            assert!((d - 1000.0).abs() < 0.01, "vertex {:?} is {} m away", c, d);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use polars::prelude::*;
+
+/// Splits a GEOMETRY field name like `POLYGON:EPSG:4326` into its geometry
+/// kind and CRS.
+pub(crate) fn split_field_name(name: &str) -> (&str, Option<&str>) {
+    match name.split_once(':') {
+        Some((kind, crs)) if !crs.is_empty() => (kind, Some(crs)),
+        Some((kind, _)) => (kind, None),
+        None => (name, None),
+    }
+}
+
+pub(crate) fn field_name(kind: &str, crs: Option<&str>) -> PlSmallStr {
+    match crs {
+        Some(crs) => format!("{}:{}", kind, crs).into(),
+        None => kind.into(),
+    }
+}
+
+/// The CRS shared by the fields of a GEOMETRY struct. Fields without a suffix
+/// don't constrain it but two different suffixes are an error.
+pub(crate) fn fields_crs<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+) -> PolarsResult<Option<PlSmallStr>> {
+    let mut found: Option<&str> = None;
+    for name in names {
+        match (found, split_field_name(name).1) {
+            (Some(a), Some(b)) if a != b => polars_bail!(
+                SchemaMismatch: "geometry fields have different CRSs, {} and {}", a, b
+            ),
+            (None, Some(b)) => found = Some(b),
+            _ => {}
+        }
+    }
+    Ok(found.map(PlSmallStr::from))
+}
+
+pub(crate) fn dtype_crs(dtype: &DataType) -> PolarsResult<Option<PlSmallStr>> {
+    match dtype {
+        DataType::Struct(fields) => fields_crs(fields.iter().map(|f| f.name().as_str())),
+        _ => polars_bail!(
+            SchemaMismatch: "geometry column has dtype {}, expected a GEOMETRY struct", dtype
+        ),
+    }
+}
+
+/// The CRS of the output of an op on several geometry inputs, inputs without a
+/// CRS, like literals, take on the CRS of the others.
+pub(crate) fn combine_crs(
+    crss: impl IntoIterator<Item = Option<PlSmallStr>>,
+) -> PolarsResult<Option<PlSmallStr>> {
+    let mut found: Option<PlSmallStr> = None;
+    for crs in crss.into_iter().flatten() {
+        match &found {
+            Some(a) => polars_ensure!(
+                a == &crs,
+                ComputeError: "geometry inputs have different CRSs, {} and {}, convert one of them first",
+                a, crs
+            ),
+            None => found = Some(crs),
+        }
+    }
+    Ok(found)
+}
+
+/// Replaces the CRS suffix of every field, `None` removes it.
+pub(crate) fn dtype_with_crs(dtype: &DataType, crs: Option<&str>) -> PolarsResult<DataType> {
+    match dtype {
+        DataType::Struct(fields) => Ok(DataType::Struct(
+            fields
+                .iter()
+                .map(|f| {
+                    let kind = split_field_name(f.name()).0;
+                    Field::new(field_name(kind, crs), f.dtype().clone())
+                })
+                .collect(),
+        )),
+        _ => polars_bail!(
+            SchemaMismatch: "geometry column has dtype {}, expected a GEOMETRY struct", dtype
+        ),
+    }
+}
+
+/// Renames the fields of a GEOMETRY struct to carry `crs`, the data isn't touched.
+pub fn set_crs(s: &Series, crs: Option<&str>) -> PolarsResult<Series> {
+    let ca = s.struct_()?.rechunk();
+    let validity = ca
+        .downcast_iter()
+        .next()
+        .and_then(|arr| arr.validity().cloned());
+    let fields: Vec<Series> = ca
+        .fields_as_series()
+        .into_iter()
+        .map(|f| {
+            let kind = split_field_name(f.name()).0;
+            let name = field_name(kind, crs);
+            f.with_name(name)
+        })
+        .collect();
+    Ok(
+        StructChunked::from_series(s.name().clone(), s.len(), fields.iter())?
+            .with_outer_validity(validity)
+            .into_series(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::geometry_dtype;
+    use crate::ops::test_utils::{geometry_series, read_geometries};
+    use crate::ops::{run_binary_geometry_op_on_structs, run_binary_op_on_structs};
+    use geo::{Geometry, Intersects, point, polygon};
+
+    fn rows() -> Vec<Option<Geometry>> {
+        vec![
+            Some(point!(x: 1., y: 1.).into()),
+            None,
+            Some(polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.)].into()),
+        ]
+    }
+
+    #[test]
+    fn set_crs_renames_the_fields_and_keeps_the_rows() {
+        let s = geometry_series(None, &rows());
+        let out = set_crs(&s, Some("EPSG:3857")).unwrap();
+        assert_eq!(out.dtype(), &geometry_dtype(Some("EPSG:3857")));
+        assert_eq!(dtype_crs(out.dtype()).unwrap().as_deref(), Some("EPSG:3857"));
+        assert_eq!(read_geometries(&out), rows());
+
+        let out = set_crs(&out, None).unwrap();
+        assert_eq!(out.dtype(), &geometry_dtype(None));
+        assert_eq!(read_geometries(&out), rows());
+    }
+
+    #[test]
+    fn fields_with_different_crss_are_an_error() {
+        let names = ["POINT:EPSG:4326", "LINESTRING", "POLYGON:EPSG:4326"];
+        assert_eq!(fields_crs(names).unwrap().as_deref(), Some("EPSG:4326"));
+        assert!(fields_crs(["POINT:EPSG:4326", "POLYGON:EPSG:3857"]).is_err());
+        assert!(dtype_crs(&DataType::Float64).is_err());
+    }
+
+    #[test]
+    fn binary_ops_raise_on_mismatched_crs() {
+        let wgs84 = geometry_series(Some("EPSG:4326"), &rows());
+        let mercator = geometry_series(Some("EPSG:3857"), &rows());
+        let intersects = |g: &Geometry, o: &Geometry| g.intersects(o);
+        let out = run_binary_op_on_structs(&[wgs84.clone(), mercator], &DataType::Boolean, intersects);
+        assert!(out.is_err());
+
+        // a literal without a CRS takes on the CRS of the other side
+        let literal = geometry_series(None, &[Some(point!(x: 1., y: 1.).into())]);
+        let out = run_binary_op_on_structs(&[wgs84.clone(), literal.clone()], &DataType::Boolean, intersects)
+            .unwrap();
+        assert_eq!(Vec::from(out.bool().unwrap()), [Some(true), None, Some(true)]);
+        let out = run_binary_geometry_op_on_structs(&[literal, wgs84], |g, _| g.clone()).unwrap();
+        assert_eq!(out.dtype(), &geometry_dtype(Some("EPSG:4326")));
+    }
+}
@@ -0,0 +1,179 @@
+use geo::{
+    Densify, Euclidean, Geodesic, Geometry, GeometryCollection, Haversine, LineString,
+    LineStringSegmentize, LineStringSegmentizeHaversine, MultiLineString, Polygon, Rhumb,
+};
+use polars::prelude::*;
+
+use super::distance::Metric;
+
+macro_rules! densified {
+    ($geom:expr, $max_segment_length:expr, $metric:expr) => {
+        match $metric {
+            Metric::Euclidean => $geom.densify::<Euclidean>($max_segment_length),
+            Metric::Haversine => $geom.densify::<Haversine>($max_segment_length),
+            // check_densify rejects vincenty, it measures on the same ellipsoid
+            Metric::Geodesic | Metric::Vincenty => $geom.densify::<Geodesic>($max_segment_length),
+            Metric::Rhumb => $geom.densify::<Rhumb>($max_segment_length),
+        }
+    };
+}
+
+/// Vincenty has no way to interpolate between points, and segments can only
+/// be split by a positive length.
+pub(crate) fn check_densify(max_segment_length: f64, metric: Metric) -> PolarsResult<()> {
+    polars_ensure!(
+        max_segment_length > 0.0,
+        InvalidOperation: "max_segment_length must be positive, got {}", max_segment_length
+    );
+    polars_ensure!(
+        metric != Metric::Vincenty,
+        InvalidOperation: "densify doesn't support vincenty, use geodesic instead"
+    );
+    Ok(())
+}
+
+/// Adds vertices so no segment is longer than `max_segment_length`. LINE
+/// becomes a LINESTRING and RECT and TRIANGLE become POLYGONs, points come
+/// back unchanged.
+pub(crate) fn densify(geom: &Geometry, max_segment_length: f64, metric: Metric) -> Geometry {
+    match geom {
+        Geometry::Line(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::LineString(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::MultiLineString(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::Polygon(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::MultiPolygon(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::Rect(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::Triangle(g) => densified!(g, max_segment_length, metric).into(),
+        Geometry::GeometryCollection(gc) => Geometry::GeometryCollection(GeometryCollection(
+            gc.iter()
+                .map(|g| densify(g, max_segment_length, metric))
+                .collect(),
+        )),
+        Geometry::Point(_) | Geometry::MultiPoint(_) => geom.clone(),
+    }
+}
+
+/// Splits `line` into `n` pieces of equal length.
+fn segmentize_line(line: &LineString, n: usize, metric: Metric) -> Option<MultiLineString> {
+    match metric {
+        Metric::Haversine => line.line_segmentize_haversine(n),
+        _ => line.line_segmentize(n),
+    }
+}
+
+fn rings(polygon: &Polygon) -> impl Iterator<Item = &LineString> {
+    std::iter::once(polygon.exterior()).chain(polygon.interiors())
+}
+
+fn segmentize_lines<'a>(
+    lines: impl IntoIterator<Item = &'a LineString>,
+    n: usize,
+    metric: Metric,
+) -> Option<MultiLineString> {
+    let mut pieces = vec![];
+    for line in lines {
+        pieces.extend(segmentize_line(line, n, metric)?);
+    }
+    Some(MultiLineString(pieces))
+}
+
+/// Only euclidean and haversine can split lines.
+pub(crate) fn check_segmentize(n: usize, metric: Metric) -> PolarsResult<()> {
+    polars_ensure!(n > 0, InvalidOperation: "line_segmentize needs at least 1 segment");
+    polars_ensure!(
+        matches!(metric, Metric::Euclidean | Metric::Haversine),
+        InvalidOperation: "line_segmentize supports euclidean and haversine metrics"
+    );
+    Ok(())
+}
+
+/// Splits every line into `n` pieces of equal length and returns all the
+/// pieces as one MULTILINESTRING. Polygons are split along their rings.
+/// `None` for points and when a piece can't be interpolated.
+pub(crate) fn line_segmentize(geom: &Geometry, n: usize, metric: Metric) -> Option<Geometry> {
+    let pieces = match geom {
+        Geometry::Line(l) => segmentize_line(&LineString::from(*l), n, metric),
+        Geometry::LineString(ls) => segmentize_line(ls, n, metric),
+        Geometry::MultiLineString(mls) => segmentize_lines(mls, n, metric),
+        Geometry::Polygon(p) => segmentize_lines(rings(p), n, metric),
+        Geometry::MultiPolygon(mp) => segmentize_lines(mp.iter().flat_map(rings), n, metric),
+        Geometry::Rect(r) => segmentize_line(r.to_polygon().exterior(), n, metric),
+        Geometry::Triangle(t) => segmentize_line(t.to_polygon().exterior(), n, metric),
+        Geometry::GeometryCollection(gc) => {
+            let mut pieces = vec![];
+            for g in gc {
+                if let Some(Geometry::MultiLineString(mls)) = line_segmentize(g, n, metric) {
+                    pieces.extend(mls);
+                }
+            }
+            Some(MultiLineString(pieces))
+        }
+        Geometry::Point(_) | Geometry::MultiPoint(_) => None,
+    };
+    pieces.map(Geometry::MultiLineString)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{CoordsIter, Line, Point, Rect, line_string, polygon};
+
+    #[test]
+    fn densify_adds_vertices_up_to_the_segment_length() {
+        let line: Geometry = line_string![(x: 0., y: 0.), (x: 10., y: 0.)].into();
+        assert_eq!(densify(&line, 3., Metric::Euclidean).coords_count(), 5);
+        assert_eq!(densify(&line, 20., Metric::Euclidean).coords_count(), 2);
+        // about 111km along the equator, split in 3 pieces of at most 50km
+        let degree: Geometry = line_string![(x: 0., y: 0.), (x: 1., y: 0.)].into();
+        for metric in [Metric::Haversine, Metric::Geodesic, Metric::Rhumb] {
+            assert_eq!(densify(&degree, 50_000., metric).coords_count(), 4);
+        }
+    }
+
+    #[test]
+    fn densify_turns_lines_and_rects_into_their_general_type() {
+        let line: Geometry = Line::new((0., 0.), (0., 4.)).into();
+        let out = densify(&line, 1., Metric::Euclidean);
+        assert!(matches!(out, Geometry::LineString(_)));
+        assert_eq!(out.coords_count(), 5);
+        let rect: Geometry = Rect::new((0., 0.), (2., 2.)).into();
+        let out = densify(&rect, 1., Metric::Euclidean);
+        assert!(matches!(out, Geometry::Polygon(_)));
+        assert_eq!(out.coords_count(), 9);
+        let point: Geometry = Point::new(1., 1.).into();
+        assert_eq!(densify(&point, 1., Metric::Euclidean), point);
+    }
+
+    #[test]
+    fn line_segmentize_splits_every_line_and_ring() {
+        let line: Geometry = line_string![(x: 0., y: 0.), (x: 3., y: 0.), (x: 3., y: 3.)].into();
+        let Some(Geometry::MultiLineString(pieces)) = line_segmentize(&line, 4, Metric::Euclidean)
+        else {
+            panic!("expected a MULTILINESTRING");
+        };
+        assert_eq!(pieces.0.len(), 4);
+        // the corner at (3, 0) sits in the middle of the second piece
+        assert_eq!(pieces.0[1].coords_count(), 3);
+
+        let polygon: Geometry = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 2.)]],
+        )
+        .into();
+        let Some(Geometry::MultiLineString(pieces)) = line_segmentize(&polygon, 2, Metric::Haversine)
+        else {
+            panic!("expected a MULTILINESTRING");
+        };
+        assert_eq!(pieces.0.len(), 4);
+        assert!(line_segmentize(&Point::new(0., 0.).into(), 2, Metric::Euclidean).is_none());
+    }
+
+    #[test]
+    fn unsupported_arguments_are_an_error() {
+        assert!(check_densify(0., Metric::Euclidean).is_err());
+        assert!(check_densify(1., Metric::Vincenty).is_err());
+        assert!(check_segmentize(0, Metric::Euclidean).is_err());
+        assert!(check_segmentize(2, Metric::Geodesic).is_err());
+        assert!(check_segmentize(2, Metric::Haversine).is_ok());
+    }
+}
@@ -0,0 +1,180 @@
+use geo::{
+    Closest, CoordsIter, Distance, Euclidean, Geodesic, Geometry, Haversine,
+    HaversineClosestPoint, Intersects, LineString, Point, Rhumb, VincentyDistance,
+};
+use polars::prelude::*;
+
+/// How distances between geometries are measured. Euclidean is in the units of
+/// the CRS, the others take lon/lat degrees and return metres.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Metric {
+    Euclidean,
+    /// Great circle distance on a sphere.
+    Haversine,
+    /// Shortest path on the WGS84 ellipsoid.
+    Geodesic,
+    /// Vincenty's iterative formula on the WGS84 ellipsoid, it doesn't
+    /// converge for nearly antipodal points.
+    Vincenty,
+    /// Along lines of constant bearing on a sphere.
+    Rhumb,
+}
+impl Metric {
+    pub fn parse(name: &str) -> PolarsResult<Metric> {
+        match name {
+            "euclidean" => Ok(Metric::Euclidean),
+            "haversine" => Ok(Metric::Haversine),
+            "geodesic" => Ok(Metric::Geodesic),
+            "vincenty" => Ok(Metric::Vincenty),
+            "rhumb" => Ok(Metric::Rhumb),
+            _ => polars_bail!(
+                InvalidOperation: "unknown metric {}, expected euclidean, haversine, geodesic, vincenty or rhumb", name
+            ),
+        }
+    }
+    pub fn is_geographic(self) -> bool {
+        self != Metric::Euclidean
+    }
+    fn point_distance(self, a: Point, b: Point) -> Option<f64> {
+        match self {
+            Metric::Euclidean => Some(Euclidean::distance(a, b)),
+            Metric::Haversine => Some(Haversine::distance(a, b)),
+            Metric::Geodesic => Some(Geodesic::distance(a, b)),
+            Metric::Vincenty => a.vincenty_distance(&b).ok(),
+            Metric::Rhumb => Some(Rhumb::distance(a, b)),
+        }
+    }
+    /// The distance from `p` to the closest point of `to`, found on great circles.
+    fn closest_distance(self, p: Point, to: &Geometry) -> Option<f64> {
+        match to.haversine_closest_point(&p) {
+            Closest::Intersection(_) => Some(0.0),
+            Closest::SinglePoint(q) => self.point_distance(p, q),
+            Closest::Indeterminate => None,
+        }
+    }
+    /// The shortest distance between `a` and `b`, in the units of the CRS for
+    /// Euclidean and in metres for the others. `None` for empty geometries and
+    /// when Vincenty doesn't converge. Between geometries that don't touch
+    /// the shortest geographic distance starts at a vertex of one of them.
+    ///
+    /// Unless both are points, the closest point is picked on great circles
+    /// with `haversine_closest_point` and only then measured with the metric,
+    /// so geodesic, vincenty and rhumb distances to lines and polygons are
+    /// approximate.
+    pub fn distance(self, a: &Geometry, b: &Geometry) -> Option<f64> {
+        if a.coords_iter().next().is_none() || b.coords_iter().next().is_none() {
+            return None;
+        }
+        match (self, a, b) {
+            (Metric::Euclidean, a, b) => Some(Euclidean::distance(a, b)),
+            (_, Geometry::Point(a), Geometry::Point(b)) => self.point_distance(*a, *b),
+            (_, Geometry::Point(a), b) => self.closest_distance(*a, b),
+            (_, a, Geometry::Point(b)) => self.closest_distance(*b, a),
+            (_, a, b) if a.intersects(b) => Some(0.0),
+            (_, a, b) => {
+                let from_vertices = |from: &Geometry, to: &Geometry| {
+                    from.coords_iter()
+                        .filter_map(|c| self.closest_distance(Point(c), to))
+                        .min_by(f64::total_cmp)
+                };
+                [from_vertices(a, b), from_vertices(b, a)]
+                    .into_iter()
+                    .flatten()
+                    .min_by(f64::total_cmp)
+            }
+        }
+    }
+    fn line_length(self, line: &LineString) -> Option<f64> {
+        line.lines()
+            .map(|l| self.point_distance(l.start_point(), l.end_point()))
+            .sum()
+    }
+    /// The length of lines, points have none and polygons are measured along
+    /// their exterior rings. `None` when Vincenty doesn't converge.
+    pub fn length(self, geom: &Geometry) -> Option<f64> {
+        match geom {
+            Geometry::Point(_) | Geometry::MultiPoint(_) => Some(0.0),
+            Geometry::Line(l) => self.point_distance(l.start_point(), l.end_point()),
+            Geometry::LineString(ls) => self.line_length(ls),
+            Geometry::MultiLineString(mls) => mls.iter().map(|ls| self.line_length(ls)).sum(),
+            Geometry::Polygon(p) => self.line_length(p.exterior()),
+            Geometry::MultiPolygon(mp) => mp.iter().map(|p| self.line_length(p.exterior())).sum(),
+            Geometry::Rect(r) => self.line_length(r.to_polygon().exterior()),
+            Geometry::Triangle(t) => self.line_length(t.to_polygon().exterior()),
+            Geometry::GeometryCollection(gc) => gc.iter().map(|g| self.length(g)).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{GeometryCollection, MultiPolygon, Rect, line_string, point};
+
+    fn assert_near(value: Option<f64>, expected: f64, tolerance: f64) {
+        let value = value.unwrap();
+        assert!((value - expected).abs() < tolerance, "{} isn't near {}", value, expected);
+    }
+
+    #[test]
+    fn length_of_a_line_in_each_metric() {
+        // two degrees along the equator
+        let line: Geometry = line_string![(x: 0., y: 0.), (x: 1., y: 0.), (x: 2., y: 0.)].into();
+        let sphere = 2. * 6371008.8 * std::f64::consts::PI / 180.;
+        let ellipsoid = 2. * 6378137. * std::f64::consts::PI / 180.;
+        assert_near(Metric::Euclidean.length(&line), 2., 1e-12);
+        assert_near(Metric::Haversine.length(&line), sphere, 1e-6);
+        assert_near(Metric::Rhumb.length(&line), sphere, 1e-6);
+        assert_near(Metric::Geodesic.length(&line), ellipsoid, 1e-6);
+        assert_near(Metric::Vincenty.length(&line), ellipsoid, 1e-3);
+
+        // one degree along a meridian is shorter on the ellipsoid than on the equator
+        let meridian: Geometry = line_string![(x: 0., y: 0.), (x: 0., y: 1.)].into();
+        assert_near(Metric::Geodesic.length(&meridian), 110574.389, 1e-3);
+        assert_near(Metric::Vincenty.length(&meridian), 110574.389, 1e-3);
+    }
+
+    #[test]
+    fn length_of_other_geometries() {
+        let rect = Rect::new((0., 0.), (3., 4.));
+        assert_near(Metric::Euclidean.length(&rect.into()), 14., 1e-12);
+        assert_near(Metric::Euclidean.length(&rect.to_polygon().into()), 14., 1e-12);
+        assert_eq!(Metric::Geodesic.length(&point!(x: 1., y: 1.).into()), Some(0.));
+        let collection = GeometryCollection(vec![
+            rect.into(),
+            line_string![(x: 0., y: 0.), (x: 3., y: 4.)].into(),
+            point!(x: 9., y: 9.).into(),
+        ]);
+        assert_near(Metric::Euclidean.length(&Geometry::GeometryCollection(collection)), 19., 1e-12);
+    }
+
+    #[test]
+    fn metrics_parse_by_name() {
+        assert!(Metric::parse("geodesic").unwrap() == Metric::Geodesic);
+        assert!(!Metric::parse("euclidean").unwrap().is_geographic());
+        assert!(Metric::parse("manhattan").is_err());
+    }
+
+    #[test]
+    fn empty_geometries_have_no_distance() {
+        let p: Geometry = point!(x: 1.0, y: 2.0).into();
+        let empties: [Geometry; 2] = [
+            LineString::<f64>::new(vec![]).into(),
+            MultiPolygon::<f64>::new(vec![]).into(),
+        ];
+        for metric in [
+            Metric::Euclidean,
+            Metric::Haversine,
+            Metric::Geodesic,
+            Metric::Vincenty,
+            Metric::Rhumb,
+        ] {
+            assert_eq!(metric.distance(&p, &p), Some(0.0));
+            for empty in &empties {
+                assert_eq!(metric.distance(&p, empty), None);
+                assert_eq!(metric.distance(empty, &p), None);
+                assert_eq!(metric.distance(empty, empty), None);
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+/// Built-in proj strings for common EPSG codes so no PROJ database is needed.
+///
+/// Covers WGS84, NAD83 and ETRS89 lon/lat, the WGS84, NAD83 and ETRS89 UTM
+/// zones, Web and World Mercator, polar stereographic and a few national grids.
+pub(crate) fn epsg_proj_string(code: u32) -> Option<String> {
+    const ETRS89: &str = "+ellps=GRS80 +towgs84=0,0,0,0,0,0,0";
+    let definition = match code {
+        4326 => "+proj=longlat +datum=WGS84 +no_defs".to_string(),
+        4269 => "+proj=longlat +datum=NAD83 +no_defs".to_string(),
+        4258 => format!("+proj=longlat {} +no_defs", ETRS89),
+        // WGS84 / UTM north and south
+        32601..=32660 => format!("+proj=utm +zone={} +datum=WGS84 +units=m +no_defs", code - 32600),
+        32701..=32760 => format!(
+            "+proj=utm +zone={} +south +datum=WGS84 +units=m +no_defs",
+            code - 32700
+        ),
+        // NAD83 / UTM zones 1N to 23N
+        26901..=26923 => format!("+proj=utm +zone={} +datum=NAD83 +units=m +no_defs", code - 26900),
+        // ETRS89 / UTM zones 28N to 38N
+        25828..=25838 => format!(
+            "+proj=utm +zone={} {} +units=m +no_defs",
+            code - 25800,
+            ETRS89
+        ),
+        // spherical web mercator, the null grid skips the datum shift like every web map does
+        3857 => concat!(
+            "+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1",
+            " +units=m +nadgrids=@null +no_defs"
+        )
+        .to_string(),
+        3395 => "+proj=merc +lon_0=0 +k=1 +x_0=0 +y_0=0 +datum=WGS84 +units=m +no_defs".to_string(),
+        // NSIDC sea ice polar stereographic north and Antarctic polar stereographic
+        3413 => concat!(
+            "+proj=stere +lat_0=90 +lat_ts=70 +lon_0=-45 +k=1 +x_0=0 +y_0=0",
+            " +datum=WGS84 +units=m +no_defs"
+        )
+        .to_string(),
+        3031 => concat!(
+            "+proj=stere +lat_0=-90 +lat_ts=-71 +lon_0=0 +k=1 +x_0=0 +y_0=0",
+            " +datum=WGS84 +units=m +no_defs"
+        )
+        .to_string(),
+        // ETRS89 / LAEA Europe
+        3035 => format!(
+            "+proj=laea +lat_0=52 +lon_0=10 +x_0=4321000 +y_0=3210000 {} +units=m +no_defs",
+            ETRS89
+        ),
+        // NAD83 / Conus Albers
+        5070 => concat!(
+            "+proj=aea +lat_0=23 +lon_0=-96 +lat_1=29.5 +lat_2=45.5 +x_0=0 +y_0=0",
+            " +datum=NAD83 +units=m +no_defs"
+        )
+        .to_string(),
+        // British National Grid
+        27700 => concat!(
+            "+proj=tmerc +lat_0=49 +lon_0=-2 +k=0.9996012717 +x_0=400000 +y_0=-100000",
+            " +ellps=airy +towgs84=446.448,-125.157,542.06,0.15,0.247,0.842,-20.489",
+            " +units=m +no_defs"
+        )
+        .to_string(),
+        // RGF93 / Lambert-93, France
+        2154 => format!(
+            "+proj=lcc +lat_0=46.5 +lon_0=3 +lat_1=49 +lat_2=44 +x_0=700000 +y_0=6600000 {} +units=m +no_defs",
+            ETRS89
+        ),
+        // CH1903+ / LV95, Switzerland
+        2056 => concat!(
+            "+proj=somerc +lat_0=46.9524055555556 +lon_0=7.43958333333333 +k_0=1",
+            " +x_0=2600000 +y_0=1200000 +ellps=bessel",
+            " +towgs84=674.374,15.056,405.346,0,0,0,0 +units=m +no_defs"
+        )
+        .to_string(),
+        // Amersfoort / RD New, Netherlands
+        28992 => concat!(
+            "+proj=sterea +lat_0=52.1561605555556 +lon_0=5.38763888888889 +k=0.9999079",
+            " +x_0=155000 +y_0=463000 +ellps=bessel",
+            " +towgs84=565.417,50.3319,465.552,-0.398957,0.343988,-1.8774,4.0725",
+            " +units=m +no_defs"
+        )
+        .to_string(),
+        // SWEREF99 TM, Sweden
+        3006 => format!("+proj=utm +zone=33 {} +units=m +no_defs", ETRS89),
+        // GDA94 / Australian Albers
+        3577 => concat!(
+            "+proj=aea +lat_0=0 +lon_0=132 +lat_1=-18 +lat_2=-36 +x_0=0 +y_0=0",
+            " +ellps=GRS80 +towgs84=0,0,0,0,0,0,0 +units=m +no_defs"
+        )
+        .to_string(),
+        // NZGD2000 / New Zealand Transverse Mercator
+        2193 => concat!(
+            "+proj=tmerc +lat_0=0 +lon_0=173 +k=0.9996 +x_0=1600000 +y_0=10000000",
+            " +ellps=GRS80 +towgs84=0,0,0,0,0,0,0 +units=m +no_defs"
+        )
+        .to_string(),
+        _ => return None,
+    };
+    Some(definition)
+}
@@ -0,0 +1,165 @@
+use geo::{BoundingRect, Geometry, Rect};
+use polars::prelude::*;
+use polars_core::POOL;
+use rayon::prelude::*;
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{AABB, RTree};
+
+use super::bounds::bounding_rects;
+use super::combine_crs;
+use super::to_geom::Geos;
+
+type Entry = GeomWithData<Rectangle<[f64; 2]>, IdxSize>;
+
+fn rectangle(rect: Rect) -> Rectangle<[f64; 2]> {
+    Rectangle::from_corners(rect.min().into(), rect.max().into())
+}
+
+fn envelope(rect: Rect) -> AABB<[f64; 2]> {
+    AABB::from_corners(rect.min().into(), rect.max().into())
+}
+
+/// An R-tree over the bounding boxes of a GEOMETRY column. Queries return the
+/// row numbers of that column.
+pub struct SpatialIndex {
+    tree: RTree<Entry>,
+    rows: usize,
+    crs: Option<PlSmallStr>,
+}
+impl SpatialIndex {
+    pub fn new(s: &Series) -> PolarsResult<SpatialIndex> {
+        SpatialIndex::from_geos(&Geos::new(s)?, s.len())
+    }
+    pub(crate) fn from_geos(geometries: &Geos, rows: usize) -> PolarsResult<SpatialIndex> {
+        let entries = bounding_rects(geometries, rows)?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, rect)| Some(Entry::new(rectangle(rect?), i as IdxSize)))
+            .collect();
+        Ok(SpatialIndex {
+            tree: RTree::bulk_load(entries),
+            rows,
+            crs: geometries.crs().cloned(),
+        })
+    }
+    /// The number of rows of the indexed column, including the ones that
+    /// aren't in the tree.
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+    pub fn crs(&self) -> Option<&PlSmallStr> {
+        self.crs.as_ref()
+    }
+    /// Rows whose bounding box intersects `rect`, in no particular order.
+    pub fn query_envelope(&self, rect: Rect) -> Vec<IdxSize> {
+        self.tree
+            .locate_in_envelope_intersecting(&envelope(rect))
+            .map(|e| e.data)
+            .collect()
+    }
+    /// Rows whose bounding box intersects the bounding box of `geom`.
+    pub fn query_geometry(&self, geom: &Geometry) -> Vec<IdxSize> {
+        geom.bounding_rect()
+            .map(|rect| self.query_envelope(rect))
+            .unwrap_or_default()
+    }
+    /// Up to `k` rows ordered by the distance from `(x, y)` to their bounding box.
+    pub fn nearest(&self, x: f64, y: f64, k: usize) -> Vec<IdxSize> {
+        self.tree
+            .nearest_neighbor_iter(&[x, y])
+            .take(k)
+            .map(|e| e.data)
+            .collect()
+    }
+    /// Bulk version of `query_geometry`, the rows of `s` are paired with the
+    /// rows of the index whose bounding boxes intersect theirs. The result has
+    /// an `input_index` and a `tree_index` column, sorted by `input_index`.
+    pub fn query(&self, s: &Series) -> PolarsResult<DataFrame> {
+        let geometries = Geos::new(s)?;
+        combine_crs([self.crs.clone(), geometries.crs().cloned()])?;
+        let matches: Vec<Vec<IdxSize>> = POOL.install(|| {
+            (0..s.len())
+                .into_par_iter()
+                .map(|i| {
+                    let geom = geometries.get_row(i)?;
+                    Ok(geom.map(|g| self.query_geometry(&g)).unwrap_or_default())
+                })
+                .collect::<PolarsResult<_>>()
+        })?;
+        let mut input_index = Vec::with_capacity(matches.iter().map(Vec::len).sum());
+        let mut tree_index = Vec::with_capacity(input_index.capacity());
+        for (i, rows) in matches.into_iter().enumerate() {
+            input_index.extend(std::iter::repeat_n(i as IdxSize, rows.len()));
+            tree_index.extend(rows);
+        }
+        DataFrame::new(vec![
+            IdxCa::from_vec("input_index".into(), input_index).into_column(),
+            IdxCa::from_vec("tree_index".into(), tree_index).into_column(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test_utils::geometry_series;
+    use geo::{LineString, line_string, point};
+
+    /// Unit squares at x = 0, 2, 4, 6 and 8, then a null and an empty row.
+    fn squares(crs: Option<&str>) -> Series {
+        let mut geoms: Vec<Option<Geometry>> = (0..5)
+            .map(|i| {
+                let x = 2. * i as f64;
+                Some(Rect::new((x, 0.), (x + 1., 1.)).to_polygon().into())
+            })
+            .collect();
+        geoms.extend([None, Some(LineString::new(vec![]).into())]);
+        geometry_series(crs, &geoms)
+    }
+
+    fn sorted(mut rows: Vec<IdxSize>) -> Vec<IdxSize> {
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn query_finds_intersecting_bounding_boxes() {
+        let index = SpatialIndex::new(&squares(None)).unwrap();
+        assert_eq!(index.len(), 7);
+        let rect = Rect::new((1.5, -1.), (4.5, 1.));
+        assert_eq!(sorted(index.query_envelope(rect)), [1, 2]);
+        assert_eq!(index.query_geometry(&point!(x: 2.5, y: 0.5).into()), [1]);
+        assert!(index.query_geometry(&point!(x: 1.5, y: 0.5).into()).is_empty());
+        assert!(index.query_geometry(&LineString::new(vec![]).into()).is_empty());
+    }
+
+    #[test]
+    fn nearest_orders_by_distance_to_the_bounding_box() {
+        let index = SpatialIndex::new(&squares(None)).unwrap();
+        assert_eq!(index.nearest(6.6, 0.5, 2), [3, 4]);
+        assert_eq!(index.nearest(-3., 0.5, 1), [0]);
+        // null and empty rows aren't in the tree
+        assert_eq!(sorted(index.nearest(0., 0., 10)), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bulk_query_pairs_input_and_tree_rows() {
+        let index = SpatialIndex::new(&squares(Some("EPSG:3857"))).unwrap();
+        let inputs = geometry_series(
+            None,
+            &[
+                Some(point!(x: 0.5, y: 0.5).into()),
+                None,
+                Some(line_string![(x: 0.5, y: 0.5), (x: 4.5, y: 0.5)].into()),
+            ],
+        );
+        let df = index.query(&inputs).unwrap();
+        let input_index: Vec<IdxSize> = df.column("input_index").unwrap().idx().unwrap().into_no_null_iter().collect();
+        let tree_index: Vec<IdxSize> = df.column("tree_index").unwrap().idx().unwrap().into_no_null_iter().collect();
+        assert_eq!(input_index, [0, 2, 2, 2]);
+        assert_eq!(tree_index[0], 0);
+        assert_eq!(sorted(tree_index[1..].to_vec()), [0, 1, 2]);
+
+        assert!(index.query(&squares(Some("EPSG:4326"))).is_err());
+    }
+}
@@ -0,0 +1,406 @@
+use geo::{BoundingRect, Centroid, Contains, Geometry, Intersects, Rect, Relate, Within};
+use polars::prelude::*;
+use polars_core::POOL;
+use rayon::prelude::*;
+
+use super::combine_crs;
+use super::distance::Metric;
+use super::index::SpatialIndex;
+use super::to_geom::Geos;
+
+/// How a left geometry has to relate to a right one for the rows to join.
+#[derive(Clone, Copy)]
+pub enum SpatialPredicate {
+    Intersects,
+    Contains,
+    Within,
+    Touches,
+    Crosses,
+    Overlaps,
+    Covers,
+    CoveredBy,
+}
+impl SpatialPredicate {
+    pub fn parse(name: &str) -> PolarsResult<SpatialPredicate> {
+        let predicate = match name {
+            "intersects" => SpatialPredicate::Intersects,
+            "contains" => SpatialPredicate::Contains,
+            "within" => SpatialPredicate::Within,
+            "touches" => SpatialPredicate::Touches,
+            "crosses" => SpatialPredicate::Crosses,
+            "overlaps" => SpatialPredicate::Overlaps,
+            "covers" => SpatialPredicate::Covers,
+            "covered_by" => SpatialPredicate::CoveredBy,
+            _ => polars_bail!(
+                InvalidOperation: "unknown predicate {}, expected one of intersects, contains, within, touches, crosses, overlaps, covers or covered_by",
+                name
+            ),
+        };
+        Ok(predicate)
+    }
+    fn test(self, left: &Geometry, right: &Geometry) -> bool {
+        match self {
+            SpatialPredicate::Intersects => left.intersects(right),
+            SpatialPredicate::Contains => left.contains(right),
+            SpatialPredicate::Within => left.is_within(right),
+            SpatialPredicate::Touches => left.relate(right).is_touches(),
+            SpatialPredicate::Crosses => left.relate(right).is_crosses(),
+            SpatialPredicate::Overlaps => left.relate(right).is_overlaps(),
+            SpatialPredicate::Covers => left.relate(right).is_covers(),
+            SpatialPredicate::CoveredBy => left.relate(right).is_coveredby(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum JoinHow {
+    Inner,
+    /// Left rows without a match are kept once with nulls on the right.
+    Left,
+}
+impl JoinHow {
+    pub fn parse(name: &str) -> PolarsResult<JoinHow> {
+        match name {
+            "inner" => Ok(JoinHow::Inner),
+            "left" => Ok(JoinHow::Left),
+            _ => polars_bail!(InvalidOperation: "unknown join type {}, expected inner or left", name),
+        }
+    }
+}
+
+fn geometry_column<'a>(df: &'a DataFrame, name: &str) -> PolarsResult<&'a Series> {
+    Ok(df.column(name)?.as_materialized_series())
+}
+
+/// Gathers the matched rows of both frames side by side, right columns whose
+/// names are taken get `suffix`.
+fn gather_pairs(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_idx: &IdxCa,
+    right_idx: &IdxCa,
+    suffix: &str,
+) -> PolarsResult<DataFrame> {
+    let mut out = left.take(left_idx)?;
+    let mut right = right.take(right_idx)?;
+    for name in right.get_column_names_owned() {
+        if out.get_column_index(&name).is_some() {
+            right.rename(&name, format!("{}{}", name, suffix).into())?;
+        }
+    }
+    out.hstack_mut(right.get_columns())?;
+    Ok(out)
+}
+
+/// Joins the rows of `left` and `right` whose geometries satisfy `predicate`.
+/// An R-tree over the right geometries finds the candidates and the predicate
+/// is tested exactly on those. Rows come out in left order, and for each left
+/// row in right order.
+pub fn sjoin(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_on: &str,
+    right_on: &str,
+    predicate: SpatialPredicate,
+    how: JoinHow,
+    suffix: &str,
+) -> PolarsResult<DataFrame> {
+    let lefts = Geos::new(geometry_column(left, left_on)?)?;
+    let rights = Geos::new(geometry_column(right, right_on)?)?;
+    combine_crs([lefts.crs().cloned(), rights.crs().cloned()])?;
+    let index = SpatialIndex::from_geos(&rights, right.height())?;
+
+    let matches: Vec<Vec<IdxSize>> = POOL.install(|| {
+        (0..left.height())
+            .into_par_iter()
+            .map(|i| {
+                let Some(l) = lefts.get_row(i)? else {
+                    return Ok(vec![]);
+                };
+                let mut rows = vec![];
+                for j in index.query_geometry(&l) {
+                    if let Some(r) = rights.get_row(j as usize)?
+                        && predicate.test(&l, &r)
+                    {
+                        rows.push(j);
+                    }
+                }
+                rows.sort_unstable();
+                Ok(rows)
+            })
+            .collect::<PolarsResult<_>>()
+    })?;
+
+    let mut left_idx: Vec<IdxSize> = Vec::with_capacity(matches.len());
+    let mut right_idx: Vec<Option<IdxSize>> = Vec::with_capacity(matches.len());
+    for (i, rows) in matches.into_iter().enumerate() {
+        if rows.is_empty() {
+            if let JoinHow::Left = how {
+                left_idx.push(i as IdxSize);
+                right_idx.push(None);
+            }
+            continue;
+        }
+        left_idx.extend(std::iter::repeat_n(i as IdxSize, rows.len()));
+        right_idx.extend(rows.into_iter().map(Some));
+    }
+    gather_pairs(
+        left,
+        right,
+        &IdxCa::from_vec(PlSmallStr::EMPTY, left_idx),
+        &IdxCa::from_iter_options(PlSmallStr::EMPTY, right_idx.into_iter()),
+        suffix,
+    )
+}
+
+/// Metres in a degree of latitude, and of longitude at the equator, rounded
+/// down so envelopes made with them are never too small.
+const MIN_METRES_PER_DEGREE: f64 = 110_000.0;
+
+/// Envelopes that hold everything within `radius` of `rect`. Geographic
+/// radii are metres, they become degrees that widen towards the poles and
+/// wrap around the antimeridian.
+fn search_envelopes(rect: Rect, radius: f64, metric: Metric) -> Vec<Rect> {
+    let (min, max) = (rect.min(), rect.max());
+    if !metric.is_geographic() {
+        return vec![Rect::new(
+            (min.x - radius, min.y - radius),
+            (max.x + radius, max.y + radius),
+        )];
+    }
+    let dlat = radius / MIN_METRES_PER_DEGREE;
+    let (south, north) = (min.y - dlat, max.y + dlat);
+    let everywhere = vec![Rect::new((-180.0, -90.0), (180.0, 90.0))];
+    if south <= -90.0 || north >= 90.0 {
+        return everywhere;
+    }
+    let widest = south.abs().max(north.abs()).to_radians().cos();
+    let dlon = radius / (MIN_METRES_PER_DEGREE * widest);
+    let (west, east) = (min.x - dlon, max.x + dlon);
+    if east - west >= 360.0 {
+        return everywhere;
+    }
+    let mut envelopes = vec![Rect::new((west.max(-180.0), south), (east.min(180.0), north))];
+    if west < -180.0 {
+        envelopes.push(Rect::new((west + 360.0, south), (180.0, north)));
+    }
+    if east > 180.0 {
+        envelopes.push(Rect::new((-180.0, south), (east - 360.0, north)));
+    }
+    envelopes
+}
+
+/// The `k` right rows closest to `left` with their distances, closest first.
+/// The exact distances to the `k` nearest bounding boxes bound the search
+/// radius, then everything within that radius is measured.
+fn nearest_rows(
+    index: &SpatialIndex,
+    rights: &Geos,
+    left: &Geometry,
+    k: usize,
+    max_distance: Option<f64>,
+    metric: Metric,
+) -> PolarsResult<Vec<(IdxSize, f64)>> {
+    let (Some(anchor), Some(rect)) = (left.centroid(), left.bounding_rect()) else {
+        return Ok(vec![]);
+    };
+    let measure = |j: IdxSize| -> PolarsResult<Option<f64>> {
+        Ok(rights
+            .get_row(j as usize)?
+            .and_then(|r| metric.distance(left, &r)))
+    };
+    let mut radius: Option<f64> = None;
+    for j in index.nearest(anchor.x(), anchor.y(), k) {
+        if let Some(d) = measure(j)? {
+            radius = Some(radius.map_or(d, |r| r.max(d)));
+        }
+    }
+    let Some(mut radius) = radius else {
+        return Ok(vec![]);
+    };
+    if let Some(max_distance) = max_distance {
+        radius = radius.min(max_distance);
+    }
+    let mut candidates: Vec<IdxSize> = search_envelopes(rect, radius, metric)
+        .into_iter()
+        .flat_map(|envelope| index.query_envelope(envelope))
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    let mut rows = vec![];
+    for j in candidates {
+        if let Some(d) = measure(j)?
+            && d <= radius
+        {
+            rows.push((j, d));
+        }
+    }
+    rows.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    rows.truncate(k);
+    Ok(rows)
+}
+
+/// Joins every left row to its `k` nearest right rows. Pairs further apart
+/// than `max_distance` don't match and `distance_col` gets the distances.
+#[allow(clippy::too_many_arguments)]
+pub fn sjoin_nearest(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_on: &str,
+    right_on: &str,
+    k: usize,
+    max_distance: Option<f64>,
+    distance_col: Option<&str>,
+    metric: Metric,
+    how: JoinHow,
+    suffix: &str,
+) -> PolarsResult<DataFrame> {
+    polars_ensure!(k > 0, InvalidOperation: "k must be at least 1");
+    let lefts = Geos::new(geometry_column(left, left_on)?)?;
+    let rights = Geos::new(geometry_column(right, right_on)?)?;
+    combine_crs([lefts.crs().cloned(), rights.crs().cloned()])?;
+    let index = SpatialIndex::from_geos(&rights, right.height())?;
+
+    let matches: Vec<Vec<(IdxSize, f64)>> = POOL.install(|| {
+        (0..left.height())
+            .into_par_iter()
+            .map(|i| match lefts.get_row(i)? {
+                Some(l) => nearest_rows(&index, &rights, &l, k, max_distance, metric),
+                None => Ok(vec![]),
+            })
+            .collect::<PolarsResult<_>>()
+    })?;
+
+    let mut left_idx: Vec<IdxSize> = Vec::with_capacity(matches.len());
+    let mut right_idx: Vec<Option<IdxSize>> = Vec::with_capacity(matches.len());
+    let mut distances: Vec<Option<f64>> = Vec::with_capacity(matches.len());
+    for (i, rows) in matches.into_iter().enumerate() {
+        if rows.is_empty() {
+            if let JoinHow::Left = how {
+                left_idx.push(i as IdxSize);
+                right_idx.push(None);
+                distances.push(None);
+            }
+            continue;
+        }
+        for (j, d) in rows {
+            left_idx.push(i as IdxSize);
+            right_idx.push(Some(j));
+            distances.push(Some(d));
+        }
+    }
+    let mut out = gather_pairs(
+        left,
+        right,
+        &IdxCa::from_vec(PlSmallStr::EMPTY, left_idx),
+        &IdxCa::from_iter_options(PlSmallStr::EMPTY, right_idx.into_iter()),
+        suffix,
+    )?;
+    if let Some(name) = distance_col {
+        out.with_column(Column::new(name.into(), distances))?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test_utils::{geometry_series, read_geometries};
+    use geo::point;
+
+    /// Points at x = 0.5, 5, 20 and a null geometry.
+    fn points() -> DataFrame {
+        let geoms: [Option<Geometry>; 4] = [
+            Some(point!(x: 0.5, y: 0.5).into()),
+            Some(point!(x: 5., y: 0.5).into()),
+            Some(point!(x: 20., y: 0.5).into()),
+            None,
+        ];
+        DataFrame::new(vec![
+            Column::new("id".into(), [0u32, 1, 2, 3]),
+            geometry_series(Some("EPSG:3857"), &geoms).into_column(),
+        ])
+        .unwrap()
+    }
+
+    /// Two overlapping rects, [0, 2] and [1, 6] along x.
+    fn squares() -> DataFrame {
+        let geoms: [Option<Geometry>; 2] = [
+            Some(Rect::new((0., 0.), (2., 1.)).into()),
+            Some(Rect::new((1., 0.), (6., 1.)).into()),
+        ];
+        DataFrame::new(vec![
+            Column::new("id".into(), ["a", "b"]),
+            geometry_series(Some("EPSG:3857"), &geoms).into_column(),
+        ])
+        .unwrap()
+    }
+
+    fn ids(df: &DataFrame) -> Vec<(u32, Option<&str>)> {
+        let left = df.column("id").unwrap().u32().unwrap();
+        let right = df.column("id_right").unwrap().str().unwrap();
+        left.into_no_null_iter().zip(right).collect()
+    }
+
+    #[test]
+    fn sjoin_inner_and_left() {
+        let (left, right) = (points(), squares());
+        let join = |how| {
+            sjoin(&left, &right, "GEOMETRY", "GEOMETRY", SpatialPredicate::Within, how, "_right")
+                .unwrap()
+        };
+        let inner = join(JoinHow::Inner);
+        assert_eq!(
+            inner.get_column_names(),
+            ["id", "GEOMETRY", "id_right", "GEOMETRY_right"]
+        );
+        assert_eq!(ids(&inner), vec![(0, Some("a")), (1, Some("b"))]);
+
+        let left_join = join(JoinHow::Left);
+        assert_eq!(
+            ids(&left_join),
+            vec![(0, Some("a")), (1, Some("b")), (2, None), (3, None)]
+        );
+        let present = |name: &str| -> Vec<bool> {
+            let s = left_join.column(name).unwrap().as_materialized_series();
+            read_geometries(s).iter().map(Option::is_some).collect()
+        };
+        assert_eq!(present("GEOMETRY"), [true, true, true, false]);
+        assert_eq!(present("GEOMETRY_right"), [true, true, false, false]);
+    }
+
+    #[test]
+    fn sjoin_nearest_with_distances() {
+        let (left, right) = (points(), squares());
+        let join = |k, max_distance, how| {
+            sjoin_nearest(
+                &left,
+                &right,
+                "GEOMETRY",
+                "GEOMETRY",
+                k,
+                max_distance,
+                Some("distance"),
+                Metric::Euclidean,
+                how,
+                "_right",
+            )
+            .unwrap()
+        };
+        let distances = |df: &DataFrame| -> Vec<Option<f64>> {
+            df.column("distance").unwrap().f64().unwrap().into_iter().collect()
+        };
+
+        let nearest = join(1, None, JoinHow::Inner);
+        assert_eq!(ids(&nearest), vec![(0, Some("a")), (1, Some("b")), (2, Some("b"))]);
+        assert_eq!(distances(&nearest), vec![Some(0.), Some(0.), Some(14.)]);
+
+        // the first point is in one square and half a unit from the other
+        let two = join(2, Some(1.0), JoinHow::Left);
+        assert_eq!(
+            ids(&two),
+            vec![(0, Some("a")), (0, Some("b")), (1, Some("b")), (2, None), (3, None)]
+        );
+        assert_eq!(distances(&two), vec![Some(0.), Some(0.5), Some(0.), None, None]);
+    }
+}
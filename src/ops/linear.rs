@@ -0,0 +1,102 @@
+use geo::{
+    Euclidean, Geometry, Length, LineInterpolatePoint, LineLocatePoint, LineString, Point,
+};
+
+/// The lines that linear referencing works along, a LINE is treated as a
+/// two point LINESTRING.
+fn as_line(geom: &Geometry) -> Option<LineString> {
+    match geom {
+        Geometry::LineString(ls) => Some(ls.clone()),
+        Geometry::Line(l) => Some(LineString::from(*l)),
+        _ => None,
+    }
+}
+
+/// Positions along a line are fractions of its length, or distances in the
+/// units of its CRS when not `normalized`, e.g. degrees for EPSG:4326. A
+/// distance has no fraction on a line without length.
+fn to_fraction(line: &LineString, position: f64, normalized: bool) -> Option<f64> {
+    if normalized {
+        return Some(position);
+    }
+    let length = line.length::<Euclidean>();
+    (length > 0.0).then(|| position / length)
+}
+
+/// The point at `position` along a line, clamped to its ends. `None` for
+/// geometries that aren't lines and for lines without length.
+pub(crate) fn interpolate_point(geom: &Geometry, position: f64, normalized: bool) -> Option<Point> {
+    let line = as_line(geom)?;
+    line.line_interpolate_point(to_fraction(&line, position, normalized)?)
+}
+
+/// The position along a line of the point on it closest to `point`, as a
+/// fraction of its length or a distance in the units of its CRS.
+pub(crate) fn locate_point(geom: &Geometry, point: &Geometry, normalized: bool) -> Option<f64> {
+    let (line, Geometry::Point(point)) = (as_line(geom)?, point) else {
+        return None;
+    };
+    let fraction = line.line_locate_point(point)?;
+    match normalized {
+        true => Some(fraction),
+        false => Some(fraction * line.length::<Euclidean>()),
+    }
+}
+
+/// The part of a line between `start` and `end`, reversed when `start` comes
+/// after `end`. Positions outside the line are clamped to its ends.
+pub(crate) fn substring(
+    geom: &Geometry,
+    start: f64,
+    end: f64,
+    normalized: bool,
+) -> Option<LineString> {
+    let line = as_line(geom)?;
+    let start = to_fraction(&line, start, normalized)?;
+    let end = to_fraction(&line, end, normalized)?;
+    if start > end {
+        let mut reversed = substring_between(&line, end, start)?;
+        reversed.0.reverse();
+        return Some(reversed);
+    }
+    substring_between(&line, start, end)
+}
+
+fn substring_between(line: &LineString, start: f64, end: f64) -> Option<LineString> {
+    let first = line.line_interpolate_point(start)?;
+    let last = line.line_interpolate_point(end)?;
+    let total = line.length::<Euclidean>();
+    let (from, to) = (start.clamp(0.0, 1.0) * total, end.clamp(0.0, 1.0) * total);
+    let mut coords = vec![first.0];
+    let mut travelled = 0.0;
+    for segment in line.lines() {
+        travelled += segment.length::<Euclidean>();
+        if travelled > from && travelled < to {
+            coords.push(segment.end);
+        }
+    }
+    coords.push(last.0);
+    Some(LineString(coords))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{line_string, point};
+
+    #[test]
+    fn distances_along_a_line_without_length_have_no_position() {
+        let line: Geometry = line_string![(x: 1., y: 1.), (x: 1., y: 1.)].into();
+        assert_eq!(interpolate_point(&line, 0.5, false), None);
+        assert_eq!(substring(&line, 0., 1., false), None);
+        assert_eq!(interpolate_point(&line, 0.5, true), None);
+    }
+
+    #[test]
+    fn distances_are_in_the_units_of_the_coordinates() {
+        let line: Geometry = line_string![(x: 0., y: 0.), (x: 4., y: 0.)].into();
+        assert_eq!(interpolate_point(&line, 1., false), Some(point!(x: 1., y: 0.)));
+        assert_eq!(locate_point(&line, &point!(x: 3., y: 2.).into(), false), Some(3.));
+        assert_eq!(locate_point(&line, &point!(x: 3., y: 2.).into(), true), Some(0.75));
+    }
+}
@@ -1,4 +1,34 @@
+mod affine;
+mod bounds;
+mod buffer;
+mod crs;
+mod densify;
+mod distance;
+mod epsg;
+mod index;
+mod join;
+mod linear;
+mod proj;
 mod run_op;
+mod simplify;
+#[cfg(test)]
+pub(crate) mod test_utils;
 mod to_geom;
 mod to_pl;
-pub use run_op::run_op_on_struct;
+pub(crate) use affine::{Origin, apply_affine, transform_around};
+pub use bounds::{bounds, bounds_dtype, envelope, total_bounds};
+pub(crate) use buffer::{Buffer, geodesic_buffer};
+pub(crate) use crs::{combine_crs, dtype_crs, dtype_with_crs, set_crs};
+pub(crate) use densify::{check_densify, check_segmentize, densify, line_segmentize};
+pub use distance::Metric;
+pub use index::SpatialIndex;
+pub use join::{JoinHow, SpatialPredicate, sjoin, sjoin_nearest};
+pub(crate) use linear::{interpolate_point, locate_point, substring};
+pub(crate) use proj::{AutoUtm, Reprojection, parse_crs};
+pub use run_op::{
+    run_binary_geometry_op_on_structs, run_binary_op_on_structs, run_geometry_agg_on_struct,
+    run_geometry_op_on_struct, run_geometry_op_with_values_on_struct, run_op_on_struct,
+};
+pub(crate) use simplify::{SimplifyMethod, simplify};
+pub(crate) use to_pl::Builder;
+pub use to_pl::geometry_dtype;
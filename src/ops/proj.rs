@@ -0,0 +1,244 @@
+use std::sync::OnceLock;
+
+use geo::{Centroid, Coord, Geometry, MapCoordsInPlace, Point};
+use polars::prelude::*;
+use proj4rs::proj::Proj;
+use proj4rs::transform::transform;
+
+use super::epsg::epsg_proj_string;
+
+/// Parses a CRS given as `EPSG:<code>` from the built-in table or as a proj
+/// string starting with `+proj=`.
+pub(crate) fn parse_crs(crs: &str) -> PolarsResult<Proj> {
+    let definition = match crs.trim() {
+        s if s.starts_with('+') => s.to_string(),
+        s => match s.split_once(':') {
+            Some((authority, code)) if authority.eq_ignore_ascii_case("EPSG") => code
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .and_then(epsg_proj_string)
+                .ok_or_else(|| {
+                    polars_err!(ComputeError: "{} isn't in the built-in EPSG table, pass a proj string instead", crs)
+                })?,
+            _ => polars_bail!(
+                ComputeError: "can't parse CRS {}, expected EPSG:<code> or a proj string", crs
+            ),
+        },
+    };
+    Proj::from_proj_string(&definition)
+        .map_err(|e| polars_err!(ComputeError: "invalid CRS {}: {}", crs, e))
+}
+
+/// Converts geometries from one CRS to another. Lon/lat coordinates are in
+/// degrees on both sides, proj4rs works in radians.
+pub(crate) struct Reprojection {
+    src: Proj,
+    dst: Proj,
+}
+impl Reprojection {
+    pub(crate) fn new(src: &str, dst: &str) -> PolarsResult<Reprojection> {
+        Ok(Reprojection {
+            src: parse_crs(src)?,
+            dst: parse_crs(dst)?,
+        })
+    }
+    pub(crate) fn apply(&self, geom: &Geometry) -> PolarsResult<Geometry> {
+        reproject(&self.src, &self.dst, geom.clone())
+    }
+}
+
+fn reproject(src: &Proj, dst: &Proj, mut geom: Geometry) -> PolarsResult<Geometry> {
+    if src.is_latlong() {
+        geom.map_coords_in_place(|c| Coord {
+            x: c.x.to_radians(),
+            y: c.y.to_radians(),
+        });
+    }
+    transform(src, dst, &mut geom)
+        .map_err(|e| polars_err!(ComputeError: "can't reproject geometry: {}", e))?;
+    if dst.is_latlong() {
+        geom.map_coords_in_place(|c| Coord {
+            x: c.x.to_degrees(),
+            y: c.y.to_degrees(),
+        });
+    }
+    Ok(geom)
+}
+
+/// The WGS84 UTM zone EPSG code, 326xx north of the equator and 327xx south,
+/// of a lon/lat position.
+fn utm_epsg(lon: f64, lat: f64) -> u32 {
+    let zone = (((lon + 180.0) / 6.0).floor() as i64).rem_euclid(60) as u32 + 1;
+    if lat >= 0.0 { 32600 + zone } else { 32700 + zone }
+}
+
+/// The UTM projections are parsed once, on first use.
+static UTM_ZONES: [OnceLock<Proj>; 120] = [const { OnceLock::new() }; 120];
+
+fn utm_proj(code: u32) -> PolarsResult<&'static Proj> {
+    let slot = match code {
+        32601..=32660 => code - 32601,
+        32701..=32760 => code - 32701 + 60,
+        _ => polars_bail!(ComputeError: "EPSG:{} isn't a WGS84 UTM zone", code),
+    };
+    let cell = &UTM_ZONES[slot as usize];
+    if let Some(proj) = cell.get() {
+        return Ok(proj);
+    }
+    let proj = parse_crs(&format!("EPSG:{}", code))?;
+    Ok(cell.get_or_init(|| proj))
+}
+
+/// Runs planar ops in metres by moving each geometry into the UTM zone of its
+/// centroid and, for ops that return geometries, back again.
+pub(crate) struct AutoUtm {
+    src: Proj,
+    lonlat: Proj,
+}
+impl AutoUtm {
+    pub(crate) fn new(src: &str) -> PolarsResult<AutoUtm> {
+        Ok(AutoUtm {
+            src: parse_crs(src)?,
+            lonlat: parse_crs("EPSG:4326")?,
+        })
+    }
+    /// The UTM zone of the geometry's centroid, `None` for empty geometries.
+    pub(crate) fn estimate(&self, geom: &Geometry) -> PolarsResult<Option<u32>> {
+        let Some(centroid) = geom.centroid() else {
+            return Ok(None);
+        };
+        let centroid = match self.src.is_latlong() {
+            true => centroid.into(),
+            false => reproject(&self.src, &self.lonlat, centroid.into())?,
+        };
+        let Geometry::Point(Point(Coord { x: lon, y: lat })) = centroid else {
+            polars_bail!(ComputeError: "reprojecting the centroid gave {:?}", centroid)
+        };
+        polars_ensure!(
+            (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat),
+            ComputeError: "centroid ({}, {}) isn't a lon/lat position, is the CRS right?", lon, lat
+        );
+        Ok(Some(utm_epsg(lon, lat)))
+    }
+    /// The geometry in its UTM zone and the zone's EPSG code.
+    pub(crate) fn project(&self, geom: &Geometry) -> PolarsResult<Option<(Geometry, u32)>> {
+        let Some(code) = self.estimate(geom)? else {
+            return Ok(None);
+        };
+        let utm = reproject(&self.src, utm_proj(code)?, geom.clone())?;
+        Ok(Some((utm, code)))
+    }
+    /// Moves a geometry from UTM `code` back to the source CRS.
+    pub(crate) fn unproject(&self, geom: Geometry, code: u32) -> PolarsResult<Geometry> {
+        reproject(utm_proj(code)?, &self.src, geom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{
+        CoordsIter, Distance, Euclidean, Geodesic, LineString, MultiPoint, Point, line_string,
+        point,
+    };
+
+    fn assert_near(geom: &Geometry, expected: &Geometry, tolerance: f64) {
+        let (a, b) = (geom.coords_iter(), expected.coords_iter());
+        for (a, b) in a.zip(b) {
+            assert!(
+                (a.x - b.x).abs() < tolerance && (a.y - b.y).abs() < tolerance,
+                "{:?} isn't near {:?}",
+                a,
+                b
+            );
+        }
+        assert_eq!(geom.coords_count(), expected.coords_count());
+    }
+
+    #[test]
+    fn wgs84_to_web_mercator() {
+        let reprojection = Reprojection::new("EPSG:4326", "EPSG:3857").unwrap();
+        let cases = [
+            (point!(x: 0., y: 0.), point!(x: 0., y: 0.)),
+            (point!(x: 180., y: 0.), point!(x: 20037508.342789244, y: 0.)),
+            (point!(x: -74.006, y: 40.7128), point!(x: -8238310.235647, y: 4970071.579142)),
+            (point!(x: 151.2093, y: -33.8688), point!(x: 16832542.279207, y: -4011198.647308)),
+        ];
+        for (lon_lat, expected) in cases {
+            let out = reprojection.apply(&lon_lat.into()).unwrap();
+            assert_near(&out, &expected.into(), 0.01);
+        }
+    }
+
+    #[test]
+    fn reprojection_roundtrips_every_coordinate() {
+        let line: Geometry = line_string![(x: -74.006, y: 40.7128), (x: 151.2093, y: -33.8688)].into();
+        let there = Reprojection::new("EPSG:4326", "EPSG:3857").unwrap();
+        let back = Reprojection::new("EPSG:3857", "EPSG:4326").unwrap();
+        let out = back.apply(&there.apply(&line).unwrap()).unwrap();
+        assert_near(&out, &line, 1e-9);
+
+        let proj_string = Reprojection::new("EPSG:4326", "+proj=merc +a=6378137 +b=6378137 +units=m").unwrap();
+        let out = proj_string.apply(&Point::new(180., 0.).into()).unwrap();
+        assert_near(&out, &point!(x: 20037508.342789244, y: 0.).into(), 0.01);
+    }
+
+    #[test]
+    fn unknown_crss_are_an_error() {
+        assert!(parse_crs("EPSG:4326").is_ok());
+        assert!(parse_crs("epsg: 3857").is_ok());
+        assert!(parse_crs("EPSG:1").is_err());
+        assert!(parse_crs("WGS84").is_err());
+        assert!(parse_crs("+proj=nonsense").is_err());
+    }
+
+    #[test]
+    fn utm_zones_at_their_edges_and_in_the_south() {
+        let cases = [
+            ((-180., 0.), 32601),
+            ((-174.0001, 10.), 32601),
+            ((-174., 10.), 32602),
+            ((179.999, 10.), 32660),
+            ((180., 10.), 32601),
+            ((3., 0.), 32631),
+            ((3., -0.0001), 32731),
+            ((-70.6, -33.4), 32719),
+            ((151.2, -33.9), 32756),
+        ];
+        for ((lon, lat), code) in cases {
+            assert_eq!(utm_epsg(lon, lat), code, "({}, {})", lon, lat);
+        }
+    }
+
+    #[test]
+    fn auto_utm_estimates_from_the_centroid_in_any_crs() {
+        let sydney: Geometry = point!(x: 151.2093, y: -33.8688).into();
+        let utm = AutoUtm::new("EPSG:4326").unwrap();
+        assert_eq!(utm.estimate(&sydney).unwrap(), Some(32756));
+        assert_eq!(utm.estimate(&MultiPoint::new(vec![]).into()).unwrap(), None);
+        // metres passed off as degrees
+        assert!(utm.estimate(&point!(x: 500000., y: 4000000.).into()).is_err());
+
+        let mercator = Reprojection::new("EPSG:4326", "EPSG:3857").unwrap().apply(&sydney).unwrap();
+        let utm = AutoUtm::new("EPSG:3857").unwrap();
+        assert_eq!(utm.estimate(&mercator).unwrap(), Some(32756));
+    }
+
+    #[test]
+    fn auto_utm_projects_into_the_zone_and_back() {
+        let utm = AutoUtm::new("EPSG:4326").unwrap();
+        // on the central meridian of zone 56
+        let line: Geometry = line_string![(x: 153., y: -33.9), (x: 153.01, y: -33.9)].into();
+        let (projected, code) = utm.project(&line).unwrap().unwrap();
+        assert_eq!(code, 32756);
+        let coords: Vec<Coord> = projected.coords_iter().collect();
+        assert!((coords[0].x - 500000.).abs() < 1e-6, "{:?}", coords[0]);
+        // UTM scales distances by 0.9996 on the central meridian
+        let metres = Euclidean::distance(coords[0], coords[1]);
+        let geodesic = Geodesic::distance(point!(x: 153., y: -33.9), point!(x: 153.01, y: -33.9));
+        assert!((metres - 0.9996 * geodesic).abs() < 0.01, "{} {}", metres, geodesic);
+        assert_near(&utm.unproject(projected, code).unwrap(), &line, 1e-9);
+        assert_eq!(utm.project(&LineString::new(vec![]).into()).unwrap(), None);
+    }
+}
@@ -1,26 +1,339 @@
-use geo::Geometry;
+use geo::{Geometry, GeometryCollection};
 
+use super::crs::combine_crs;
 use super::to_pl::{Builder, GeomOpResult};
 use crate::ops::to_geom::Geos;
 use polars::prelude::*;
+use polars_core::POOL;
+use rayon::prelude::*;
+use std::ops::Range;
 
-pub fn run_op_on_struct<'a, F, T>(
+/// Runs `f` on every geometry and writes the values to a Series of `dtype`,
+/// the dtype the expression declares. It's used as is when every row is null.
+pub fn run_op_on_struct<F, T>(
     inputs: &[Series],
+    dtype: &DataType,
     f: F,
 ) -> PolarsResult<Series>
 where
-    F: Fn(&Geometry) -> T,
+    F: Fn(&Geometry) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    run_op(inputs, Output::Values(dtype), f)
+}
+
+/// Like `run_op_on_struct` but geometries are written to a full GEOMETRY struct
+/// so the output can be the input of another op.
+pub fn run_geometry_op_on_struct<F, T>(
+    inputs: &[Series],
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    run_op(inputs, Output::Geometry, f)
+}
+
+/// Aggregates a whole GEOMETRY column, or a group of one, into a single row.
+/// The non-null rows are collected into one GEOMETRYCOLLECTION for `f`.
+pub fn run_geometry_agg_on_struct<F, T>(
+    inputs: &[Series],
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: FnOnce(&Geometry) -> T,
     T: Into<GeomOpResult>,
 {
     let s = &inputs[0];
-    let rows = s.len();
-    let geometries = Geos::new(s);
-    let mut builder = Builder::new(rows);
-    for i in 0..rows {
-        match &geometries.get_row(i) {
-            Some(geom) => builder.add(f(geom).into()),
-            None => builder.add_null(),
+    let geometries = Geos::new(s)?;
+    let rows = (0..s.len())
+        .filter_map(|i| geometries.get_row(i).transpose())
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let collection = Geometry::GeometryCollection(GeometryCollection(rows));
+    let mut builder = Output::Geometry.builder(1, geometries.crs().map(|crs| crs.as_str()));
+    builder.add(f(&collection).into())?;
+    builder.finish()
+}
+
+/// Whether an op writes plain values of a dtype or GEOMETRY structs, which
+/// carry the CRS of the input.
+#[derive(Clone, Copy)]
+enum Output<'a> {
+    Values(&'a DataType),
+    Geometry,
+}
+impl Output<'_> {
+    fn builder(self, size: usize, crs: Option<&str>) -> Builder {
+        match self {
+            Output::Values(_) => Builder::new(size),
+            Output::Geometry => Builder::new_geometry(size, crs),
+        }
+    }
+    fn finish(self, builder: Builder) -> PolarsResult<Series> {
+        match self {
+            Output::Values(dtype) => {
+                let s = builder.finish_typed(dtype)?;
+                polars_ensure!(
+                    s.dtype() == dtype,
+                    SchemaMismatch: "op returned {} values, expected {}", s.dtype(), dtype
+                );
+                Ok(s)
+            }
+            Output::Geometry => builder.finish(),
         }
     }
-    Ok(builder.finish())
+}
+
+fn run_op<F, T>(
+    inputs: &[Series],
+    output: Output<'_>,
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    let s = &inputs[0];
+    let geometries = Geos::new(s)?;
+    let crs = geometries.crs().map(|crs| crs.as_str());
+    par_chunks(s.len(), |chunk| {
+        let mut builder = output.builder(chunk.len(), crs);
+        for i in chunk {
+            match &geometries.get_row(i)? {
+                Some(geom) => builder.add(f(geom).into()).map_err(|e| row_err(e, i))?,
+                None => builder.add_null(),
+            }
+        }
+        output.finish(builder)
+    })
+}
+
+/// Like `run_geometry_op_on_struct` but every input after the geometries is
+/// cast to Float64 and its value for the row is passed to `f`. Any input can
+/// be a length 1 literal, rows where one of them is null come out null.
+pub fn run_geometry_op_with_values_on_struct<F, T>(
+    inputs: &[Series],
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry, &[f64]) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    let lengths: Vec<usize> = inputs.iter().map(|s| s.len()).collect();
+    let rows = lengths.iter().copied().max().unwrap_or(0);
+    polars_ensure!(
+        lengths.iter().all(|&len| len == rows || len == 1),
+        ShapeMismatch: "inputs have lengths {:?}, expected equal lengths or length 1 literals",
+        lengths
+    );
+    let geometries = BinaryInput::new(&inputs[0])?;
+    let values = inputs[1..]
+        .iter()
+        .map(|s| Ok(s.cast(&DataType::Float64)?.f64()?.rechunk()))
+        .collect::<PolarsResult<Vec<Float64Chunked>>>()?;
+    let crs = geometries.geometries.crs().map(|crs| crs.as_str());
+    par_chunks(rows, |chunk| {
+        let mut builder = Output::Geometry.builder(chunk.len(), crs);
+        for i in chunk {
+            let row_values: Option<Vec<f64>> = values
+                .iter()
+                .map(|v| v.get(if v.len() == 1 { 0 } else { i }))
+                .collect();
+            geometries.with_row(i, |g| match (g, row_values) {
+                (Some(g), Some(row_values)) => {
+                    builder.add(f(g, &row_values).into()).map_err(|e| row_err(e, i))
+                }
+                _ => {
+                    builder.add_null();
+                    Ok(())
+                }
+            })?;
+        }
+        builder.finish()
+    })
+}
+
+/// Inputs shorter than this are evaluated on the calling thread.
+const MIN_CHUNK_ROWS: usize = 1024;
+
+/// Splits `0..rows` into chunks that are evaluated on the Polars thread pool,
+/// so `POLARS_MAX_THREADS` controls the parallelism. The results are
+/// concatenated in row order.
+fn par_chunks<E>(rows: usize, eval: E) -> PolarsResult<Series>
+where
+    E: Fn(Range<usize>) -> PolarsResult<Series> + Sync,
+{
+    let n_chunks = (rows / MIN_CHUNK_ROWS).clamp(1, POOL.current_num_threads() * 4);
+    if n_chunks == 1 {
+        return eval(0..rows);
+    }
+    let chunk_size = rows.div_ceil(n_chunks);
+    let chunks = POOL.install(|| {
+        (0..n_chunks)
+            .into_par_iter()
+            .map(|c| eval((c * chunk_size).min(rows)..((c + 1) * chunk_size).min(rows)))
+            .collect::<PolarsResult<Vec<Series>>>()
+    })?;
+    concat_chunks(chunks)
+}
+
+fn concat_chunks(chunks: Vec<Series>) -> PolarsResult<Series> {
+    let mut chunks = chunks.into_iter();
+    let mut out = chunks.next().unwrap_or_default();
+    for s in chunks {
+        out.append(&s)?;
+    }
+    Ok(out)
+}
+
+fn row_err(e: PolarsError, row: usize) -> PolarsError {
+    e.wrap_msg(|msg| format!("row {}: {}", row, msg))
+}
+
+/// One side of a binary op. A length-1 input is a literal, its geometry is
+/// extracted once and reused for every row of the other side.
+struct BinaryInput {
+    geometries: Geos,
+    literal: Option<Option<Geometry>>,
+}
+impl BinaryInput {
+    fn new(s: &Series) -> PolarsResult<BinaryInput> {
+        let geometries = Geos::new(s)?;
+        let literal = match s.len() {
+            1 => Some(geometries.get_row(0)?),
+            _ => None,
+        };
+        Ok(BinaryInput {
+            geometries,
+            literal,
+        })
+    }
+    fn with_row<R>(
+        &self,
+        row: usize,
+        f: impl FnOnce(Option<&Geometry>) -> PolarsResult<R>,
+    ) -> PolarsResult<R> {
+        match &self.literal {
+            Some(geom) => f(geom.as_ref()),
+            None => f(self.geometries.get_row(row)?.as_ref()),
+        }
+    }
+}
+
+pub fn run_binary_op_on_structs<F, T>(
+    inputs: &[Series],
+    dtype: &DataType,
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry, &Geometry) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    run_binary_op(inputs, Output::Values(dtype), f)
+}
+
+pub fn run_binary_geometry_op_on_structs<F, T>(
+    inputs: &[Series],
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry, &Geometry) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    run_binary_op(inputs, Output::Geometry, f)
+}
+
+fn run_binary_op<F, T>(
+    inputs: &[Series],
+    output: Output<'_>,
+    f: F,
+) -> PolarsResult<Series>
+where
+    F: Fn(&Geometry, &Geometry) -> T + Sync,
+    T: Into<GeomOpResult>,
+{
+    let (left, right) = (&inputs[0], &inputs[1]);
+    let rows = match (left.len(), right.len()) {
+        (l, r) if l == r => l,
+        (1, r) => r,
+        (l, 1) => l,
+        (l, r) => polars_bail!(
+            ShapeMismatch: "geometry inputs have lengths {} and {}, expected equal lengths or a length 1 literal",
+            l, r
+        ),
+    };
+    let left = BinaryInput::new(left)?;
+    let right = BinaryInput::new(right)?;
+    let crs = combine_crs([
+        left.geometries.crs().cloned(),
+        right.geometries.crs().cloned(),
+    ])?;
+    par_chunks(rows, |chunk| {
+        let mut builder = output.builder(chunk.len(), crs.as_deref());
+        for i in chunk {
+            left.with_row(i, |l| {
+                right.with_row(i, |r| match (l, r) {
+                    (Some(l), Some(r)) => builder.add(f(l, r).into()).map_err(|e| row_err(e, i)),
+                    _ => {
+                        builder.add_null();
+                        Ok(())
+                    }
+                })
+            })?;
+        }
+        output.finish(builder)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test_utils::geometry_series;
+    use geo::{Area, Intersects, point, polygon};
+
+    fn squares(n: usize) -> Series {
+        let geoms: Vec<Option<Geometry>> = (0..n)
+            .map(|i| {
+                let size = (i % 7) as f64;
+                let square = polygon![(x: 0., y: 0.), (x: size, y: 0.), (x: size, y: size), (x: 0., y: size)];
+                (i % 5 != 0).then(|| square.into())
+            })
+            .collect();
+        geometry_series(None, &geoms)
+    }
+
+    #[test]
+    fn value_ops_keep_their_dtype_on_null_and_empty_input() {
+        let point = geometry_series(None, &[Some(point!(x: 1., y: 1.).into())]);
+        for s in [squares(0), geometry_series(None, &[None, None, None])] {
+            let area = run_op_on_struct(std::slice::from_ref(&s), &DataType::Float64, |g| g.unsigned_area())
+                .unwrap();
+            assert_eq!(area.dtype(), &DataType::Float64);
+            assert_eq!(area.len(), s.len());
+            let hits = run_binary_op_on_structs(&[s.clone(), point.clone()], &DataType::Boolean, |g, o| {
+                g.intersects(o)
+            })
+            .unwrap();
+            assert_eq!(hits.dtype(), &DataType::Boolean);
+            assert_eq!(hits.len(), s.len());
+        }
+    }
+
+    #[test]
+    fn value_ops_reject_values_of_another_dtype() {
+        let out = run_op_on_struct(&[squares(3)], &DataType::Boolean, |g| g.unsigned_area());
+        assert!(out.is_err());
+    }
+
+    #[test]
+    fn chunks_come_back_in_row_order() {
+        let rows = MIN_CHUNK_ROWS * 5 + 17;
+        let area = run_op_on_struct(&[squares(rows)], &DataType::Float64, |g| g.unsigned_area())
+            .unwrap();
+        let expected: Float64Chunked = (0..rows)
+            .map(|i| (i % 5 != 0).then(|| ((i % 7) * (i % 7)) as f64))
+            .collect();
+        assert!(area.f64().unwrap().equal_missing(&expected).all());
+    }
 }
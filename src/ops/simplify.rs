@@ -0,0 +1,119 @@
+use geo::{Geometry, GeometryCollection, Simplify, SimplifyVw, SimplifyVwPreserve};
+use polars::prelude::*;
+
+#[derive(Clone, Copy)]
+pub enum SimplifyMethod {
+    /// Ramer-Douglas-Peucker, drops vertices closer than the tolerance to the
+    /// simplified line.
+    Rdp,
+    /// Visvalingam-Whyatt, drops vertices whose triangle with their neighbours
+    /// has an area under the tolerance.
+    Vw,
+    /// Visvalingam-Whyatt that doesn't let rings or lines cross themselves or
+    /// each other.
+    VwPreserve,
+}
+impl SimplifyMethod {
+    pub fn parse(name: &str) -> PolarsResult<SimplifyMethod> {
+        match name {
+            "rdp" => Ok(SimplifyMethod::Rdp),
+            "vw" => Ok(SimplifyMethod::Vw),
+            "vw_preserve" => Ok(SimplifyMethod::VwPreserve),
+            _ => polars_bail!(
+                InvalidOperation: "unknown simplify method {}, expected rdp, vw or vw_preserve", name
+            ),
+        }
+    }
+}
+
+macro_rules! simplified {
+    ($geom:expr, $tolerance:expr, $method:expr) => {
+        match $method {
+            SimplifyMethod::Rdp => $geom.simplify($tolerance),
+            SimplifyMethod::Vw => $geom.simplify_vw($tolerance),
+            SimplifyMethod::VwPreserve => $geom.simplify_vw_preserve($tolerance),
+        }
+    };
+}
+
+/// Simplifies lines and polygons, keeping the geometry type. Points and the
+/// fixed size types have nothing to drop so they come back unchanged.
+pub(crate) fn simplify(geom: &Geometry, tolerance: f64, method: SimplifyMethod) -> Geometry {
+    match geom {
+        Geometry::LineString(g) => simplified!(g, &tolerance, method).into(),
+        Geometry::MultiLineString(g) => simplified!(g, &tolerance, method).into(),
+        Geometry::Polygon(g) => simplified!(g, &tolerance, method).into(),
+        Geometry::MultiPolygon(g) => simplified!(g, &tolerance, method).into(),
+        Geometry::GeometryCollection(gc) => Geometry::GeometryCollection(GeometryCollection(
+            gc.iter()
+                .map(|g| simplify(g, tolerance, method))
+                .collect(),
+        )),
+        Geometry::Point(_)
+        | Geometry::MultiPoint(_)
+        | Geometry::Line(_)
+        | Geometry::Rect(_)
+        | Geometry::Triangle(_) => geom.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{CoordsIter, LineString, MultiPolygon, Polygon, line_string, point, polygon};
+
+    const METHODS: [SimplifyMethod; 3] = [
+        SimplifyMethod::Rdp,
+        SimplifyMethod::Vw,
+        SimplifyMethod::VwPreserve,
+    ];
+
+    fn zigzag() -> LineString {
+        line_string![(x: 0., y: 0.), (x: 1., y: 0.1), (x: 2., y: 0.), (x: 3., y: 0.1), (x: 4., y: 0.)]
+    }
+
+    /// A 10x10 square with a vertex barely off each edge.
+    fn square() -> Polygon {
+        polygon![
+            (x: 0., y: 0.), (x: 5., y: 0.1), (x: 10., y: 0.), (x: 9.9, y: 5.),
+            (x: 10., y: 10.), (x: 5., y: 9.9), (x: 0., y: 10.), (x: 0.1, y: 5.),
+        ]
+    }
+
+    #[test]
+    fn every_method_keeps_the_geometry_type() {
+        for method in METHODS {
+            let simplified = simplify(&zigzag().into(), 0.5, method);
+            assert_eq!(simplified, line_string![(x: 0., y: 0.), (x: 4., y: 0.)].into());
+
+            let Geometry::Polygon(p) = simplify(&square().into(), 0.5, method) else {
+                panic!("a polygon simplified to another type");
+            };
+            assert_eq!(p.exterior().coords_count(), 5);
+
+            let multi = MultiPolygon(vec![square(), square()]).into();
+            match simplify(&multi, 0.5, method) {
+                Geometry::MultiPolygon(mp) => assert_eq!(mp.0.len(), 2),
+                other => panic!("a multipolygon simplified to {:?}", other),
+            }
+
+            let collection = Geometry::GeometryCollection(GeometryCollection(vec![
+                zigzag().into(),
+                point!(x: 1., y: 1.).into(),
+            ]));
+            let Geometry::GeometryCollection(gc) = simplify(&collection, 0.5, method) else {
+                panic!("a collection simplified to another type");
+            };
+            assert!(matches!(gc.0[..], [Geometry::LineString(_), Geometry::Point(_)]));
+        }
+    }
+
+    #[test]
+    fn a_small_tolerance_keeps_every_vertex() {
+        for method in METHODS {
+            assert_eq!(simplify(&zigzag().into(), 0.01, method), zigzag().into());
+        }
+        assert!(SimplifyMethod::parse("rdp").is_ok());
+        assert!(SimplifyMethod::parse("topology").is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use geo::Geometry;
+use polars::prelude::*;
+
+use super::to_geom::Geos;
+use super::to_pl::Builder;
+
+/// A full GEOMETRY struct column in `crs` holding `geoms`.
+pub(crate) fn geometry_series(crs: Option<&str>, geoms: &[Option<Geometry>]) -> Series {
+    let mut builder = Builder::new_geometry(geoms.len(), crs);
+    for geom in geoms {
+        match geom {
+            Some(geom) => builder.add(geom.clone().into()).unwrap(),
+            None => builder.add_null(),
+        }
+    }
+    builder.finish().unwrap().with_name("GEOMETRY".into())
+}
+
+/// Reads every row of a GEOMETRY column back.
+pub(crate) fn read_geometries(s: &Series) -> Vec<Option<Geometry>> {
+    let geometries = Geos::new(s).unwrap();
+    (0..s.len()).map(|i| geometries.get_row(i).unwrap()).collect()
+}
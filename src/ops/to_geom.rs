@@ -1,252 +1,501 @@
 use geo::{
-    Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint, MultiPolygon, Orient,
-    Point, Polygon, orient::Direction,
+    Coord, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Orient, Point, Polygon, Rect, Triangle, orient::Direction,
 };
 
+use super::crs::{fields_crs, split_field_name};
+use super::to_pl::GeomTypes;
 use polars::prelude::*;
-use std::collections::HashMap;
-use std::collections::HashSet;
-enum ChunkedArrays {
-    Point(ChunkedArray<FixedSizeListType>),
-    MultiPoint(ChunkedArray<ListType>),
-    LineString(ChunkedArray<ListType>),
-    MultiLineString(ChunkedArray<ListType>),
-    Polygon(ChunkedArray<ListType>),
-    MultiPolygon(ChunkedArray<ListType>),
-}
-
-trait UncheckedAsSeries {
-    unsafe fn unchecked_as_series(&self, idx: usize) -> Series;
-}
-
-impl UncheckedAsSeries for ChunkedArray<FixedSizeListType> {
-    #[inline]
-    unsafe fn unchecked_as_series(&self, idx: usize) -> Series {
-        unsafe {
-            let inner = self.value_unchecked(idx);
-            Series::from_chunks_and_dtype_unchecked(
-                self.name().clone(),
-                vec![inner],
-                &self.inner_dtype().to_physical(),
-            )
+use polars_arrow::array::{Array, FixedSizeListArray, ListArray, PrimitiveArray, StructArray};
+use polars_arrow::bitmap::Bitmap;
+use polars_arrow::buffer::Buffer;
+use polars_arrow::offset::OffsetsBuffer;
+use std::ops::Range;
+
+fn downcast<T: 'static>(arr: &dyn Array) -> PolarsResult<&T> {
+    arr.as_any().downcast_ref::<T>().ok_or_else(|| {
+        polars_err!(SchemaMismatch: "unexpected arrow array of type {:?}", arr.dtype())
+    })
+}
+
+/// A bitmap without nulls is as good as no bitmap, dropping it enables the fast paths.
+fn validity(arr: &dyn Array) -> Option<Bitmap> {
+    arr.validity().filter(|v| v.unset_bits() > 0).cloned()
+}
+
+/// The flat x,y buffer of an `Array(f64, 2)` array, coordinate `i` is
+/// `values[2 * i]`, `values[2 * i + 1]`.
+struct Coords {
+    values: Buffer<f64>,
+    validity: Option<Bitmap>,
+    value_validity: Option<Bitmap>,
+}
+impl Coords {
+    fn new(arr: &dyn Array) -> PolarsResult<Coords> {
+        let arr = downcast::<FixedSizeListArray>(arr)?;
+        polars_ensure!(
+            arr.size() == 2,
+            SchemaMismatch: "coordinates have {} values, expected 2", arr.size()
+        );
+        let values = downcast::<PrimitiveArray<f64>>(arr.values().as_ref())?;
+        Ok(Coords {
+            values: values.values().clone(),
+            validity: validity(arr),
+            value_validity: validity(values),
+        })
+    }
+    /// `None` for a null coordinate.
+    fn get(&self, i: usize) -> PolarsResult<Option<Coord>> {
+        if self.validity.as_ref().is_some_and(|v| !v.get_bit(i)) {
+            return Ok(None);
+        }
+        if let Some(v) = &self.value_validity {
+            polars_ensure!(
+                v.get_bit(2 * i) && v.get_bit(2 * i + 1),
+                ComputeError: "coordinate has a null x or y"
+            );
         }
+        Ok(Some(Coord {
+            x: self.values[2 * i],
+            y: self.values[2 * i + 1],
+        }))
     }
-}
-impl UncheckedAsSeries for ChunkedArray<ListType> {
-    #[inline]
-    unsafe fn unchecked_as_series(&self, idx: usize) -> Series {
-        unsafe {
-            let inner = self.value_unchecked(idx);
-            Series::from_chunks_and_dtype_unchecked(
-                self.name().clone(),
-                vec![inner],
-                &self.inner_dtype().to_physical(),
-            )
+    /// The bounding box of the non-null coordinates in `range`.
+    fn bounds(&self, range: Range<usize>) -> PolarsResult<Option<Rect>> {
+        if self.validity.is_none() && self.value_validity.is_none() {
+            let mut coords = self.values[2 * range.start..2 * range.end]
+                .chunks_exact(2)
+                .map(|c| Coord { x: c[0], y: c[1] });
+            let Some(first) = coords.next() else {
+                return Ok(None);
+            };
+            let (min, max) = coords.fold((first, first), |(min, max), c| {
+                (
+                    Coord { x: min.x.min(c.x), y: min.y.min(c.y) },
+                    Coord { x: max.x.max(c.x), y: max.y.max(c.y) },
+                )
+            });
+            return Ok(Some(Rect::new(min, max)));
         }
+        let mut bounds = None;
+        for i in range {
+            if let Some(c) = self.get(i)? {
+                bounds = union(bounds, Some(Rect::new(c, c)));
+            }
+        }
+        Ok(bounds)
     }
-}
-fn add_to(
-    hashmap: &mut HashMap<PlSmallStr, (ChunkedArrays, HashSet<usize>)>,
-    name: PlSmallStr,
-    cas_null: (ChunkedArrays, HashSet<usize>),
-) {
-    if hashmap.contains_key(&name) {
-        // TODO: reconcile multiple of same column type
-        panic!("multiple {} found", name)
+    /// The non-null coordinates in `range`.
+    fn collect(&self, range: Range<usize>) -> PolarsResult<Vec<Coord>> {
+        if self.validity.is_none() && self.value_validity.is_none() {
+            return Ok(self.values[2 * range.start..2 * range.end]
+                .chunks_exact(2)
+                .map(|c| Coord { x: c[0], y: c[1] })
+                .collect());
+        }
+        range.filter_map(|i| self.get(i).transpose()).collect()
     }
-    hashmap.insert(name, cas_null);
 }
-pub(crate) struct Geos {
-    _hashmap: HashMap<PlSmallStr, (ChunkedArrays, HashSet<usize>)>,
+
+/// The offsets of one list level, they index into the level below.
+struct Offsets {
+    offsets: OffsetsBuffer<i64>,
+    validity: Option<Bitmap>,
 }
-impl Geos {
-    pub(crate) fn new(struct_col: &Series) -> Geos {
-        let ca_struct = struct_col.struct_().unwrap();
-        let geometries_series = ca_struct.fields_as_series();
-        let mut geometries: HashMap<PlSmallStr, (ChunkedArrays, HashSet<usize>)> = HashMap::new();
-        geometries_series.into_iter().for_each(|s| {
-            let nulls = s.is_null();
-            let null_set: HashSet<usize> = nulls
-                .iter()
-                .enumerate()
-                .filter_map(|(i, x)| match x {
-                    Some(x) => match x {
-                        true => Some(i),
-                        false => None,
-                    },
-                    None => None,
-                })
-                .collect();
-            if s.name().starts_with("POINT") {
-                add_to(
-                    &mut geometries,
-                    "POINT".into(),
-                    (ChunkedArrays::Point(s.array().unwrap().clone()), null_set),
-                );
-            } else if s.name().starts_with("MULTIPOINT") {
-                add_to(
-                    &mut geometries,
-                    "MULTIPOINT".into(),
-                    (
-                        ChunkedArrays::MultiPoint(s.list().unwrap().clone()),
-                        null_set,
-                    ),
-                );
-            } else if s.name().starts_with("LINESTRING") {
-                add_to(
-                    &mut geometries,
-                    "LINESTRING".into(),
-                    (
-                        ChunkedArrays::LineString(s.list().unwrap().clone()),
-                        null_set,
-                    ),
-                );
-            } else if s.name().starts_with("MULTILINESTRING") {
-                add_to(
-                    &mut geometries,
-                    "MULTILINESTRING".into(),
-                    (
-                        ChunkedArrays::MultiLineString(s.list().unwrap().clone()),
-                        null_set,
-                    ),
-                );
-            } else if s.name().starts_with("POLYGON") {
-                add_to(
-                    &mut geometries,
-                    "POLYGON".into(),
-                    (ChunkedArrays::Polygon(s.list().unwrap().clone()), null_set),
-                );
-            } else if s.name().starts_with("MULTIPOLYGON") {
-                add_to(
-                    &mut geometries,
-                    "MULTIPOLYGON".into(),
-                    (
-                        ChunkedArrays::MultiPolygon(s.list().unwrap().clone()),
-                        null_set,
-                    ),
-                );
-            } else {
-                panic!("bad column {}", s.name())
-            }
-        });
-        Geos {
-            _hashmap: geometries,
+impl Offsets {
+    fn new(arr: &dyn Array) -> PolarsResult<(Offsets, &dyn Array)> {
+        let arr = downcast::<ListArray<i64>>(arr)?;
+        let offsets = Offsets {
+            offsets: arr.offsets().clone(),
+            validity: validity(arr),
+        };
+        Ok((offsets, arr.values().as_ref()))
+    }
+    fn is_valid(&self, i: usize) -> bool {
+        self.validity.as_ref().is_none_or(|v| v.get_bit(i))
+    }
+    fn range(&self, i: usize) -> Range<usize> {
+        let (start, end) = self.offsets.start_end(i);
+        start..end
+    }
+    /// The entries of the level below that `range` spans, `None` when this
+    /// level has nulls, which have to be skipped one by one.
+    fn span(&self, range: Range<usize>) -> Option<Range<usize>> {
+        if self.validity.is_some() {
+            return None;
         }
+        let offsets = self.offsets.buffer();
+        Some(offsets[range.start] as usize..offsets[range.end] as usize)
     }
+}
 
-    pub(crate) fn get_row(&self, row: usize) -> Option<Geometry> {
-        let mut geoms: Vec<Geometry> = self
-            ._hashmap
-            .iter()
-            .filter_map(|(_, (cas, nulls))| {
-                if nulls.contains(&row) {
-                    return None;
-                }
-                Some(match cas {
-                    ChunkedArrays::Point(p) => {
-                        let s = unsafe { p.unchecked_as_series(row) };
-
-                        chunked_to_point(s.f64().unwrap()).into()
-                    }
-                    ChunkedArrays::MultiPoint(p) => {
-                        let s = unsafe { p.unchecked_as_series(row) };
-
-                        chunked_to_multipoint(s.array().unwrap()).into()
-                    }
-                    ChunkedArrays::LineString(p) => {
-                        let s = unsafe { p.unchecked_as_series(row) };
-
-                        chunked_to_linestring(s.array().unwrap()).into()
-                    }
-                    ChunkedArrays::MultiLineString(p) => {
-                        let s = unsafe { p.unchecked_as_series(row) };
-
-                        chunked_to_multilinestring(s.list().unwrap()).into()
-                    }
-                    ChunkedArrays::Polygon(p) => {
-                        let s = unsafe { p.unchecked_as_series(row) };
-
-                        chunked_to_polygon(s.list().unwrap()).into()
-                    }
-                    ChunkedArrays::MultiPolygon(p) => {
-                        let s = unsafe { p.unchecked_as_series(row) };
+fn union(a: Option<Rect>, b: Option<Rect>) -> Option<Rect> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(Rect::new(
+            Coord { x: a.min().x.min(b.min().x), y: a.min().y.min(b.min().y) },
+            Coord { x: a.max().x.max(b.max().x), y: a.max().y.max(b.max().y) },
+        )),
+        (a, b) => a.or(b),
+    }
+}
 
-                        chunked_to_multipolygon(s.list().unwrap()).into()
-                    }
-                })
-            })
-            .collect();
-        match geoms.len() {
-            0 => None,
-            1 => Some(geoms.remove(0)),
-            _ => Some(Geometry::GeometryCollection(GeometryCollection::new_from(
-                geoms,
-            ))),
-        }
+/// The bounding box of the coordinates of the valid lists in `range` of `lists`.
+fn lists_bounds(lists: &Offsets, coords: &Coords, range: Range<usize>) -> PolarsResult<Option<Rect>> {
+    if let Some(span) = lists.span(range.clone()) {
+        return coords.bounds(span);
     }
+    let mut bounds = None;
+    for i in range.filter(|&i| lists.is_valid(i)) {
+        bounds = union(bounds, coords.bounds(lists.range(i))?);
+    }
+    Ok(bounds)
 }
 
-pub(crate) fn chunked_to_point(ca: &ChunkedArray<Float64Type>) -> Point {
-    let point: Point = (ca.get(0).unwrap(), ca.get(1).unwrap()).into();
-    point
+enum GeomArray {
+    Point(Coords),
+    MultiPoint(Offsets, Coords),
+    LineString(Offsets, Coords),
+    MultiLineString(Offsets, Offsets, Coords),
+    Polygon(Offsets, Offsets, Coords),
+    MultiPolygon(Offsets, Offsets, Offsets, Coords),
+    /// LINE, RECT and TRIANGLE hold a fixed number of coordinates per row.
+    Fixed(GeomTypes, usize, Coords),
+    GeometryCollection(Offsets, Box<Geos>),
 }
-pub(crate) fn chunked_to_points(ca: &ChunkedArray<FixedSizeListType>) -> Vec<Point> {
-    let points: Vec<Point> = ca
-        .amortized_iter()
-        .filter_map(|s3| match s3 {
-            Some(s3) => {
-                let s3 = s3.as_ref();
-                let ca_coords = s3.f64().unwrap();
-                Some(chunked_to_point(ca_coords))
+impl GeomArray {
+    fn new(geom: GeomTypes, arr: &dyn Array) -> PolarsResult<GeomArray> {
+        Ok(match geom {
+            GeomTypes::Point => GeomArray::Point(Coords::new(arr)?),
+            GeomTypes::MultiPoint | GeomTypes::LineString => {
+                let (offsets, coords) = Offsets::new(arr)?;
+                let coords = Coords::new(coords)?;
+                match geom {
+                    GeomTypes::MultiPoint => GeomArray::MultiPoint(offsets, coords),
+                    _ => GeomArray::LineString(offsets, coords),
+                }
+            }
+            GeomTypes::MultiLineString | GeomTypes::Polygon => {
+                let (offsets, rings) = Offsets::new(arr)?;
+                let (rings, coords) = Offsets::new(rings)?;
+                let coords = Coords::new(coords)?;
+                match geom {
+                    GeomTypes::MultiLineString => {
+                        GeomArray::MultiLineString(offsets, rings, coords)
+                    }
+                    _ => GeomArray::Polygon(offsets, rings, coords),
+                }
+            }
+            GeomTypes::MultiPolygon => {
+                let (offsets, polygons) = Offsets::new(arr)?;
+                let (polygons, rings) = Offsets::new(polygons)?;
+                let (rings, coords) = Offsets::new(rings)?;
+                GeomArray::MultiPolygon(offsets, polygons, rings, Coords::new(coords)?)
+            }
+            GeomTypes::Line | GeomTypes::Rect | GeomTypes::Triangle => {
+                let arr = downcast::<FixedSizeListArray>(arr)?;
+                GeomArray::Fixed(geom, arr.size(), Coords::new(arr.values().as_ref())?)
+            }
+            GeomTypes::GeometryCollection => {
+                let (offsets, members) = Offsets::new(arr)?;
+                let members = Geos::from_array(downcast::<StructArray>(members)?)?;
+                GeomArray::GeometryCollection(offsets, Box::new(members))
             }
-            None => None,
         })
-        .collect();
-    points
-}
-pub(crate) fn chunked_to_linestring(ca: &ChunkedArray<FixedSizeListType>) -> LineString {
-    let points = chunked_to_points(ca);
-    LineString::from(points)
-}
-pub(crate) fn chunked_to_multipoint(ca: &ChunkedArray<FixedSizeListType>) -> MultiPoint {
-    let points = chunked_to_points(ca);
-    MultiPoint(points)
-}
-pub(crate) fn chunked_to_linestrings(ca: &ChunkedArray<ListType>) -> Vec<LineString> {
-    ca.amortized_iter()
-        .map(|s2| match s2 {
-            Some(s2) => {
-                let s2 = s2.as_ref();
-                let ca_points = s2.array().unwrap();
-                chunked_to_linestring(ca_points)
+    }
+    /// The bounding box of a row from the coordinate buffers, without
+    /// building the geometry.
+    fn bounds(&self, row: usize) -> PolarsResult<Option<Rect>> {
+        match self {
+            GeomArray::Point(coords) => coords.bounds(row..row + 1),
+            GeomArray::MultiPoint(offsets, coords) | GeomArray::LineString(offsets, coords) => {
+                coords.bounds(offsets.range(row))
+            }
+            GeomArray::MultiLineString(offsets, rings, coords)
+            | GeomArray::Polygon(offsets, rings, coords) => {
+                lists_bounds(rings, coords, offsets.range(row))
+            }
+            GeomArray::MultiPolygon(offsets, polygons, rings, coords) => {
+                if let Some(span) = polygons.span(offsets.range(row)) {
+                    return lists_bounds(rings, coords, span);
+                }
+                let mut bounds = None;
+                for p in offsets.range(row).filter(|&p| polygons.is_valid(p)) {
+                    bounds = union(bounds, lists_bounds(rings, coords, polygons.range(p))?);
+                }
+                Ok(bounds)
             }
-            None => {
-                let empty: Vec<(f64, f64)> = vec![];
-                LineString::from(empty)
+            GeomArray::Fixed(_, n, coords) => coords.bounds(row * n..(row + 1) * n),
+            GeomArray::GeometryCollection(offsets, members) => {
+                let mut bounds = None;
+                for i in offsets.range(row) {
+                    bounds = union(bounds, members.bounds(i)?);
+                }
+                Ok(bounds)
+            }
+        }
+    }
+    fn read(&self, row: usize) -> PolarsResult<Geometry> {
+        Ok(match self {
+            GeomArray::Point(coords) => match coords.get(row)? {
+                Some(c) => Point::from(c).into(),
+                None => polars_bail!(ComputeError: "coordinate is null"),
+            },
+            GeomArray::MultiPoint(offsets, coords) => {
+                let points = coords.collect(offsets.range(row))?;
+                MultiPoint::new(points.into_iter().map(Point::from).collect()).into()
+            }
+            GeomArray::LineString(offsets, coords) => {
+                LineString::new(coords.collect(offsets.range(row))?).into()
+            }
+            GeomArray::MultiLineString(offsets, rings, coords) => {
+                MultiLineString::new(read_rings(rings, coords, offsets.range(row))?).into()
+            }
+            GeomArray::Polygon(offsets, rings, coords) => {
+                read_polygon(rings, coords, offsets.range(row))?.into()
+            }
+            GeomArray::MultiPolygon(offsets, polygons, rings, coords) => {
+                let polygons = offsets
+                    .range(row)
+                    .filter(|&p| polygons.is_valid(p))
+                    .map(|p| read_polygon(rings, coords, polygons.range(p)))
+                    .collect::<PolarsResult<Vec<Polygon>>>()?;
+                MultiPolygon::new(polygons).into()
+            }
+            GeomArray::Fixed(geom, n, coords) => {
+                let c = coords.collect(row * n..(row + 1) * n)?;
+                polars_ensure!(
+                    c.len() == *n,
+                    ComputeError: "expected {} non-null coordinates, found {}", n, c.len()
+                );
+                match geom {
+                    GeomTypes::Line => Line::new(c[0], c[1]).into(),
+                    GeomTypes::Rect => Rect::new(c[0], c[1]).into(),
+                    _ => Triangle::new(c[0], c[1], c[2]).into(),
+                }
+            }
+            GeomArray::GeometryCollection(offsets, members) => {
+                let geoms = offsets
+                    .range(row)
+                    .filter_map(|i| members.get_row(i).transpose())
+                    .collect::<PolarsResult<Vec<Geometry>>>()?;
+                Geometry::GeometryCollection(GeometryCollection::new_from(geoms))
             }
         })
-        .collect()
+    }
 }
-pub(crate) fn chunked_to_multilinestring(ca: &ChunkedArray<ListType>) -> MultiLineString {
-    MultiLineString(chunked_to_linestrings(ca))
+
+/// Null rings are read as empty line strings.
+fn read_rings(rings: &Offsets, coords: &Coords, range: Range<usize>) -> PolarsResult<Vec<LineString>> {
+    range
+        .map(|r| match rings.is_valid(r) {
+            true => Ok(LineString::new(coords.collect(rings.range(r))?)),
+            false => Ok(LineString::new(vec![])),
+        })
+        .collect()
 }
-pub(crate) fn chunked_to_polygon(ca: &ChunkedArray<ListType>) -> Polygon {
-    let mut linestrings = chunked_to_linestrings(ca);
+fn read_polygon(rings: &Offsets, coords: &Coords, range: Range<usize>) -> PolarsResult<Polygon> {
+    let mut linestrings = read_rings(rings, coords, range)?;
+    polars_ensure!(!linestrings.is_empty(), ComputeError: "polygon has no exterior ring");
     let exterior = linestrings.remove(0);
     let geo_poly = Polygon::new(exterior, linestrings);
-    geo_poly.orient(Direction::Default)
-}
-pub(crate) fn chunked_to_multipolygon(ca: &ChunkedArray<ListType>) -> MultiPolygon {
-    let multis: Vec<Polygon> = ca
-        .amortized_iter()
-        .filter_map(|s3| match s3 {
-            Some(s3) => {
-                let s3 = s3.as_ref();
-                let polygon = s3.list().unwrap();
-                Some(chunked_to_polygon(polygon))
-            }
-            None => None,
+    Ok(geo_poly.orient(Direction::Default))
+}
+
+struct Field {
+    name: PlSmallStr,
+    validity: Option<Bitmap>,
+    array: GeomArray,
+}
+
+/// Reads geometries straight out of the arrow buffers of a GEOMETRY struct.
+pub(crate) struct Geos {
+    validity: Option<Bitmap>,
+    fields: Vec<Field>,
+    crs: Option<PlSmallStr>,
+}
+impl Geos {
+    pub(crate) fn new(struct_col: &Series) -> PolarsResult<Geos> {
+        let ca_struct = struct_col.struct_().map_err(|_| {
+            polars_err!(
+                SchemaMismatch: "geometry column {} has dtype {}, expected a GEOMETRY struct",
+                struct_col.name(), struct_col.dtype()
+            )
+        })?;
+        let ca_struct = ca_struct.rechunk();
+        match ca_struct.downcast_iter().next() {
+            Some(arr) => Geos::from_array(arr),
+            None => Ok(Geos {
+                validity: None,
+                fields: vec![],
+                crs: None,
+            }),
+        }
+    }
+    fn from_array(arr: &StructArray) -> PolarsResult<Geos> {
+        let mut fields: Vec<Field> = vec![];
+        for (field, values) in arr.fields().iter().zip(arr.values()) {
+            let name = field.name.clone();
+            let dtype = DataType::from_arrow_field(field);
+            // anything after the first ':' is the CRS, e.g. POLYGON:EPSG:4326
+            let kind = split_field_name(&name).0;
+            let geom = match kind {
+                "POINT" => GeomTypes::Point,
+                "MULTIPOINT" => GeomTypes::MultiPoint,
+                "LINE" => GeomTypes::Line,
+                "LINESTRING" | "LINEARRING" => GeomTypes::LineString,
+                "MULTILINESTRING" | "MULTILINEARRING" => GeomTypes::MultiLineString,
+                "POLYGON" => GeomTypes::Polygon,
+                "MULTIPOLYGON" => GeomTypes::MultiPolygon,
+                "RECT" => GeomTypes::Rect,
+                "TRIANGLE" => GeomTypes::Triangle,
+                "GEOMETRYCOLLECTION" => GeomTypes::GeometryCollection,
+                _ => polars_bail!(
+                    SchemaMismatch: "unknown geometry field {}, expected one of POINT, MULTIPOINT, LINE, LINESTRING, MULTILINESTRING, POLYGON, MULTIPOLYGON, RECT, TRIANGLE or GEOMETRYCOLLECTION",
+                    name
+                ),
+            };
+            // collection members are checked when their struct is read
+            let expected = geom.dtype();
+            let dtype_ok = match geom {
+                GeomTypes::GeometryCollection => {
+                    matches!(&dtype, DataType::List(inner) if matches!(**inner, DataType::Struct(_)))
+                }
+                _ => dtype == expected,
+            };
+            polars_ensure!(
+                dtype_ok,
+                SchemaMismatch: "geometry field {} has dtype {}, expected {}",
+                name, dtype, expected
+            );
+            // TODO: reconcile multiple of same column type
+            polars_ensure!(
+                !fields.iter().any(|f| split_field_name(&f.name).0 == kind),
+                Duplicate: "geometry struct has more than one {} field", kind
+            );
+            let array = GeomArray::new(geom, values.as_ref())
+                .map_err(|e| e.wrap_msg(|msg| format!("field {}: {}", name, msg)))?;
+            fields.push(Field {
+                name,
+                validity: validity(values.as_ref()),
+                array,
+            });
+        }
+        let crs = fields_crs(fields.iter().map(|f| f.name.as_str()))?;
+        Ok(Geos {
+            validity: validity(arr),
+            fields,
+            crs,
         })
-        .collect();
-    MultiPolygon(multis)
+    }
+    pub(crate) fn crs(&self) -> Option<&PlSmallStr> {
+        self.crs.as_ref()
+    }
+
+    /// The bounding box of a row, `None` for null and empty rows.
+    pub(crate) fn bounds(&self, row: usize) -> PolarsResult<Option<Rect>> {
+        if self.validity.as_ref().is_some_and(|v| !v.get_bit(row)) {
+            return Ok(None);
+        }
+        let mut bounds = None;
+        for f in self
+            .fields
+            .iter()
+            .filter(|f| f.validity.as_ref().is_none_or(|v| v.get_bit(row)))
+        {
+            let field_bounds = f.array.bounds(row).map_err(|e| {
+                e.wrap_msg(|msg| format!("row {} of field {}: {}", row, f.name, msg))
+            })?;
+            bounds = union(bounds, field_bounds);
+        }
+        Ok(bounds)
+    }
+    pub(crate) fn get_row(&self, row: usize) -> PolarsResult<Option<Geometry>> {
+        if self.validity.as_ref().is_some_and(|v| !v.get_bit(row)) {
+            return Ok(None);
+        }
+        let mut geoms = self
+            .fields
+            .iter()
+            .filter(|f| f.validity.as_ref().is_none_or(|v| v.get_bit(row)))
+            .map(|f| {
+                f.array.read(row).map_err(|e| {
+                    e.wrap_msg(|msg| format!("row {} of field {}: {}", row, f.name, msg))
+                })
+            });
+        let Some(first) = geoms.next().transpose()? else {
+            return Ok(None);
+        };
+        let rest = geoms.collect::<PolarsResult<Vec<Geometry>>>()?;
+        if rest.is_empty() {
+            return Ok(Some(first));
+        }
+        let mut members = vec![first];
+        members.extend(rest);
+        Ok(Some(Geometry::GeometryCollection(
+            GeometryCollection::new_from(members),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test_utils::{geometry_series, read_geometries};
+    use geo::{
+        Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Triangle, line_string,
+        point, polygon,
+    };
+
+    /// One row of every geometry type with nulls between them. Polygons are
+    /// in the default orientation that rows are read back in.
+    fn every_type() -> Vec<Option<Geometry>> {
+        let square = polygon![
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 2.), (x: 2., y: 1.)]],
+        ];
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 0.)];
+        let geoms: Vec<Geometry> = vec![
+            point!(x: 1., y: 2.).into(),
+            MultiPoint::from(vec![(0., 0.), (3., 4.)]).into(),
+            Line::new((0., 0.), (5., 5.)).into(),
+            line.clone().into(),
+            MultiLineString(vec![line.clone(), LineString::new(vec![])]).into(),
+            square.clone().into(),
+            MultiPolygon(vec![square.clone(), polygon![(x: 9., y: 9.), (x: 10., y: 9.), (x: 10., y: 10.)]]).into(),
+            Rect::new((0., 0.), (2., 3.)).into(),
+            Triangle::from([(0., 0.), (1., 0.), (0., 1.)]).into(),
+            Geometry::GeometryCollection(GeometryCollection(vec![
+                point!(x: 7., y: 7.).into(),
+                square.into(),
+                line.into(),
+            ])),
+        ];
+        geoms.into_iter().flat_map(|g| [Some(g), None]).collect()
+    }
+
+    #[test]
+    fn every_geometry_type_roundtrips() {
+        let geoms = every_type();
+        assert_eq!(read_geometries(&geometry_series(None, &geoms)), geoms);
+    }
+
+    #[test]
+    fn sliced_and_chunked_columns_roundtrip() {
+        let geoms = every_type();
+        let s = geometry_series(Some("EPSG:4326"), &geoms);
+        for (offset, len) in [(1, geoms.len() - 2), (5, 7), (geoms.len() - 1, 1), (3, 0)] {
+            assert_eq!(
+                read_geometries(&s.slice(offset as i64, len)),
+                geoms[offset..offset + len].to_vec()
+            );
+        }
+
+        let mut chunked = s.slice(0, 7);
+        chunked.append(&s.slice(7, geoms.len() - 7)).unwrap();
+        chunked.append(&s.slice(3, 4)).unwrap();
+        assert_eq!(chunked.n_chunks(), 3);
+        let mut expected = geoms.clone();
+        expected.extend_from_slice(&geoms[3..7]);
+        assert_eq!(read_geometries(&chunked), expected);
+    }
 }
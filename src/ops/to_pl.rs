@@ -1,26 +1,13 @@
 use geo::{
-    Centroid, GeodesicArea, Geometry, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Orient,
-    Point, Polygon, orient::Direction,
+    Coord, Geometry, GeometryCollection, Line, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon, Rect, Triangle,
 };
-use polars::chunked_array::builder::get_list_builder;
+use super::crs::field_name;
 use polars::prelude::*;
-use polars::prelude::*;
-use polars_arrow::array::{
-    MutableArray, MutableFixedSizeListArray, MutablePrimitiveArray, TryPush,
-};
+use polars_arrow::array::{FixedSizeListArray, ListArray, PrimitiveArray};
+use polars_arrow::bitmap::{Bitmap, MutableBitmap};
 
-pub fn points_to_series(p: &[Point]) -> PolarsResult<Series> {
-    let avs: Vec<AnyValue> = p
-        .into_iter()
-        .map(|p| AnyValue::List(Series::new(PlSmallStr::EMPTY, vec![p.x(), p.y()])))
-        .collect();
-    Series::from_any_values_and_dtype(
-        PlSmallStr::EMPTY,
-        &avs,
-        &DataType::Array(Box::new(DataType::Float64), 2),
-        true,
-    )
-}
+#[derive(Clone, Copy, PartialEq)]
 pub enum GeomTypes {
     Point,
     MultiPoint,
@@ -29,7 +16,86 @@ pub enum GeomTypes {
     MultiLineString,
     Polygon,
     MultiPolygon,
+    Rect,
+    Triangle,
+    GeometryCollection,
+}
+impl GeomTypes {
+    pub const ALL: [GeomTypes; 10] = [
+        GeomTypes::Point,
+        GeomTypes::MultiPoint,
+        GeomTypes::Line,
+        GeomTypes::LineString,
+        GeomTypes::MultiLineString,
+        GeomTypes::Polygon,
+        GeomTypes::MultiPolygon,
+        GeomTypes::Rect,
+        GeomTypes::Triangle,
+        GeomTypes::GeometryCollection,
+    ];
+    /// The types a GEOMETRYCOLLECTION element can be, nested collections are flattened.
+    pub const COLLECTION_MEMBERS: [GeomTypes; 9] = [
+        GeomTypes::Point,
+        GeomTypes::MultiPoint,
+        GeomTypes::Line,
+        GeomTypes::LineString,
+        GeomTypes::MultiLineString,
+        GeomTypes::Polygon,
+        GeomTypes::MultiPolygon,
+        GeomTypes::Rect,
+        GeomTypes::Triangle,
+    ];
+    /// The GEOMETRY field name, with the CRS suffix when there is one.
+    pub fn field_name(&self, crs: Option<&str>) -> PlSmallStr {
+        field_name(&self.name(), crs)
+    }
+    pub fn name(&self) -> PlSmallStr {
+        PlSmallStr::from_static(match self {
+            GeomTypes::Point => "POINT",
+            GeomTypes::MultiPoint => "MULTIPOINT",
+            GeomTypes::Line => "LINE",
+            GeomTypes::LineString => "LINESTRING",
+            GeomTypes::MultiLineString => "MULTILINESTRING",
+            GeomTypes::Polygon => "POLYGON",
+            GeomTypes::MultiPolygon => "MULTIPOLYGON",
+            GeomTypes::Rect => "RECT",
+            GeomTypes::Triangle => "TRIANGLE",
+            GeomTypes::GeometryCollection => "GEOMETRYCOLLECTION",
+        })
+    }
+    pub fn dtype(&self) -> DataType {
+        let coord = DataType::Array(Box::new(DataType::Float64), 2);
+        match self {
+            GeomTypes::Point => coord,
+            GeomTypes::MultiPoint | GeomTypes::LineString => DataType::List(Box::new(coord)),
+            GeomTypes::MultiLineString | GeomTypes::Polygon => {
+                DataType::List(Box::new(DataType::List(Box::new(coord))))
+            }
+            GeomTypes::MultiPolygon => DataType::List(Box::new(DataType::List(Box::new(
+                DataType::List(Box::new(coord)),
+            )))),
+            // RECT is stored as [min, max]
+            GeomTypes::Line | GeomTypes::Rect => DataType::Array(Box::new(coord), 2),
+            GeomTypes::Triangle => DataType::Array(Box::new(coord), 3),
+            GeomTypes::GeometryCollection => DataType::List(Box::new(DataType::Struct(
+                GeomTypes::COLLECTION_MEMBERS
+                    .iter()
+                    .map(|geom| Field::new(geom.name(), geom.dtype()))
+                    .collect(),
+            ))),
+        }
+    }
 }
+/// The dtype of a GEOMETRY struct with a field for every geometry type.
+pub fn geometry_dtype(crs: Option<&str>) -> DataType {
+    DataType::Struct(
+        GeomTypes::ALL
+            .iter()
+            .map(|geom| Field::new(geom.field_name(crs), geom.dtype()))
+            .collect(),
+    )
+}
+
 pub enum GeomOpResult {
     Null,
     Point(Point),
@@ -39,7 +105,14 @@ pub enum GeomOpResult {
     MultiLineString(MultiLineString),
     Polygon(Polygon),
     MultiPolygon(MultiPolygon),
+    Rect(Rect),
+    Triangle(Triangle),
+    GeometryCollection(GeometryCollection),
     Float(f64),
+    Boolean(bool),
+    String(String),
+    /// The op failed on this row, the error is raised with the row number.
+    Error(PolarsError),
 }
 
 impl From<Point> for GeomOpResult {
@@ -77,11 +150,36 @@ impl From<MultiPolygon> for GeomOpResult {
         GeomOpResult::MultiPolygon(x)
     }
 }
+impl From<Rect> for GeomOpResult {
+    fn from(x: Rect) -> Self {
+        GeomOpResult::Rect(x)
+    }
+}
+impl From<Triangle> for GeomOpResult {
+    fn from(x: Triangle) -> Self {
+        GeomOpResult::Triangle(x)
+    }
+}
+impl From<GeometryCollection> for GeomOpResult {
+    fn from(x: GeometryCollection) -> Self {
+        GeomOpResult::GeometryCollection(x)
+    }
+}
 impl From<f64> for GeomOpResult {
     fn from(value: f64) -> Self {
         GeomOpResult::Float(value)
     }
 }
+impl From<bool> for GeomOpResult {
+    fn from(value: bool) -> Self {
+        GeomOpResult::Boolean(value)
+    }
+}
+impl From<String> for GeomOpResult {
+    fn from(value: String) -> Self {
+        GeomOpResult::String(value)
+    }
+}
 impl<T> From<Option<T>> for GeomOpResult
 where
     T: Into<GeomOpResult>,
@@ -93,6 +191,17 @@ where
         }
     }
 }
+impl<T> From<PolarsResult<T>> for GeomOpResult
+where
+    T: Into<GeomOpResult>,
+{
+    fn from(result: PolarsResult<T>) -> Self {
+        match result {
+            Ok(value) => value.into(),
+            Err(e) => GeomOpResult::Error(e),
+        }
+    }
+}
 impl From<Geometry> for GeomOpResult {
     fn from(x: Geometry) -> Self {
         match x {
@@ -103,277 +212,512 @@ impl From<Geometry> for GeomOpResult {
             Geometry::MultiLineString(p)=>GeomOpResult::MultiLineString(p),
             Geometry::Polygon(p)=>GeomOpResult::Polygon(p),
             Geometry::MultiPolygon(p)=>GeomOpResult::MultiPolygon(p),
-            _=>panic!("missing geom type from geoopresult {:?}",x)
+            Geometry::Rect(p)=>GeomOpResult::Rect(p),
+            Geometry::Triangle(p)=>GeomOpResult::Triangle(p),
+            Geometry::GeometryCollection(p)=>GeomOpResult::GeometryCollection(p),
+        }
+    }
+}
+impl GeomOpResult {
+    fn geom_type(&self) -> PolarsResult<Option<GeomTypes>> {
+        let geom_type = match self {
+            GeomOpResult::Null => None,
+            GeomOpResult::Point(_) => Some(GeomTypes::Point),
+            GeomOpResult::MultiPoint(_) => Some(GeomTypes::MultiPoint),
+            GeomOpResult::Line(_) => Some(GeomTypes::Line),
+            GeomOpResult::LineString(_) => Some(GeomTypes::LineString),
+            GeomOpResult::MultiLineString(_) => Some(GeomTypes::MultiLineString),
+            GeomOpResult::Polygon(_) => Some(GeomTypes::Polygon),
+            GeomOpResult::MultiPolygon(_) => Some(GeomTypes::MultiPolygon),
+            GeomOpResult::Rect(_) => Some(GeomTypes::Rect),
+            GeomOpResult::Triangle(_) => Some(GeomTypes::Triangle),
+            GeomOpResult::GeometryCollection(_) => Some(GeomTypes::GeometryCollection),
+            GeomOpResult::Float(_) | GeomOpResult::Boolean(_) | GeomOpResult::String(_) => {
+                polars_bail!(SchemaMismatch: "a GEOMETRY struct can't hold a {} value", self.kind())
+            }
+            GeomOpResult::Error(_) => {
+                polars_bail!(ComputeError: "a failed op has no geometry type")
+            }
+        };
+        Ok(geom_type)
+    }
+    fn kind(&self) -> &'static str {
+        match self {
+            GeomOpResult::Float(_) => "float",
+            GeomOpResult::Boolean(_) => "boolean",
+            GeomOpResult::String(_) => "string",
+            GeomOpResult::Null => "null",
+            GeomOpResult::Error(_) => "error",
+            _ => "geometry",
         }
     }
 }
 
+fn flatten_collection(gc: GeometryCollection) -> Vec<Geometry> {
+    gc.0.into_iter()
+        .flat_map(|g| match g {
+            Geometry::GeometryCollection(inner) => flatten_collection(inner),
+            g => vec![g],
+        })
+        .collect()
+}
+
+/// Writes coordinates straight into the flat x,y values buffer and the list
+/// offsets above it. Only the outermost level, the row, can be null.
+pub struct CoordsBuilder {
+    values: Vec<f64>,
+    /// One offsets buffer per list level, outermost first.
+    offsets: Vec<Vec<i64>>,
+    validity: MutableBitmap,
+    list_dtypes: Vec<ArrowDataType>,
+    /// LINE, RECT and TRIANGLE rows are a fixed size list of coordinates.
+    fixed: Option<(ArrowDataType, usize)>,
+    coord_dtype: ArrowDataType,
+}
+
+impl CoordsBuilder {
+    fn new(geom: GeomTypes, size: usize) -> CoordsBuilder {
+        let mut dtype = geom.dtype().to_arrow(CompatLevel::newest());
+        let mut list_dtypes = vec![];
+        while let ArrowDataType::LargeList(field) = &dtype {
+            let inner = field.dtype().clone();
+            list_dtypes.push(std::mem::replace(&mut dtype, inner));
+        }
+        let fixed = match &dtype {
+            ArrowDataType::FixedSizeList(field, n)
+                if matches!(field.dtype(), ArrowDataType::FixedSizeList(..)) =>
+            {
+                let (inner, n) = (field.dtype().clone(), *n);
+                Some((std::mem::replace(&mut dtype, inner), n))
+            }
+            _ => None,
+        };
+        let offsets = list_dtypes
+            .iter()
+            .map(|_| {
+                let mut offsets = Vec::with_capacity(size + 1);
+                offsets.push(0);
+                offsets
+            })
+            .collect();
+        let coords_per_row = fixed.as_ref().map_or(1, |(_, n)| *n);
+        CoordsBuilder {
+            values: Vec::with_capacity(size * coords_per_row * 2),
+            offsets,
+            validity: MutableBitmap::with_capacity(size),
+            list_dtypes,
+            fixed,
+            coord_dtype: dtype,
+        }
+    }
+    /// Appends the innermost list, or the whole row for POINT, LINE, RECT and TRIANGLE.
+    fn push_coords(&mut self, coords: impl IntoIterator<Item = Coord>) {
+        coords.into_iter().for_each(|c| {
+            self.values.push(c.x);
+            self.values.push(c.y);
+        });
+        match self.offsets.len() {
+            0 => self.validity.push(true),
+            depth => self.close(depth - 1),
+        }
+    }
+    /// Ends the list at `level` after everything pushed to the level below it.
+    fn close(&mut self, level: usize) {
+        let len = match self.offsets.get(level + 1) {
+            Some(inner) => inner.len() - 1,
+            None => self.values.len() / 2,
+        };
+        self.offsets[level].push(len as i64);
+        if level == 0 {
+            self.validity.push(true);
+        }
+    }
+    fn push_null(&mut self) {
+        match self.offsets.first_mut() {
+            Some(offsets) => offsets.push(*offsets.last().unwrap_or(&0)),
+            None => {
+                let n = self.fixed.as_ref().map_or(1, |(_, n)| *n);
+                self.values.extend(std::iter::repeat_n(0.0, n * 2));
+            }
+        }
+        self.validity.push(false);
+    }
+    fn finish(self) -> PolarsResult<Series> {
+        let validity: Bitmap = self.validity.into();
+        let mut validity = Some(validity).filter(|v| v.unset_bits() > 0);
+        let rows_are_coords = self.offsets.is_empty() && self.fixed.is_none();
+        let n_coords = self.values.len() / 2;
+        let mut arr = FixedSizeListArray::try_new(
+            self.coord_dtype,
+            n_coords,
+            PrimitiveArray::from_vec(self.values).boxed(),
+            if rows_are_coords { validity.take() } else { None },
+        )?
+        .boxed();
+        if let Some((dtype, n)) = self.fixed {
+            arr = FixedSizeListArray::try_new(dtype, n_coords / n, arr, validity.take())?.boxed();
+        }
+        for (level, (dtype, offsets)) in self
+            .list_dtypes
+            .into_iter()
+            .zip(self.offsets)
+            .enumerate()
+            .rev()
+        {
+            let level_validity = if level == 0 { validity.take() } else { None };
+            arr = ListArray::<i64>::try_new(dtype, offsets.try_into()?, arr, level_validity)?
+                .boxed();
+        }
+        Series::from_arrow(PlSmallStr::EMPTY, arr)
+    }
+}
+
+/// GEOMETRYCOLLECTION rows are offsets into one struct of all their members.
+pub struct CollectionBuilder {
+    offsets: Vec<i64>,
+    validity: MutableBitmap,
+    members: StructBuilder,
+}
+
+impl CollectionBuilder {
+    fn new(size: usize) -> CollectionBuilder {
+        let mut offsets = Vec::with_capacity(size + 1);
+        offsets.push(0);
+        CollectionBuilder {
+            offsets,
+            validity: MutableBitmap::with_capacity(size),
+            members: StructBuilder::new(size, &GeomTypes::COLLECTION_MEMBERS, None),
+        }
+    }
+    fn push(&mut self, gc: GeometryCollection) -> PolarsResult<()> {
+        for g in flatten_collection(gc) {
+            self.members.add(g.into())?;
+        }
+        self.offsets.push(self.members.len as i64);
+        self.validity.push(true);
+        Ok(())
+    }
+    fn push_null(&mut self) {
+        self.offsets.push(self.members.len as i64);
+        self.validity.push(false);
+    }
+    fn finish(self) -> PolarsResult<Series> {
+        let members = self.members.finish(PlSmallStr::EMPTY)?.rechunk();
+        let validity: Bitmap = self.validity.into();
+        let arr = ListArray::<i64>::try_new(
+            GeomTypes::GeometryCollection
+                .dtype()
+                .to_arrow(CompatLevel::newest()),
+            self.offsets.try_into()?,
+            members.to_arrow(0, CompatLevel::newest()),
+            Some(validity).filter(|v| v.unset_bits() > 0),
+        )?;
+        Series::from_arrow(PlSmallStr::EMPTY, arr.boxed())
+    }
+}
 
 pub enum Builder {
     Pending((usize, usize)),
     Scalar(PrimitiveChunkedBuilder<Float64Type>),
-    Point(MutableFixedSizeListArray<MutablePrimitiveArray<f64>>),
-    MultiPoint(Box<dyn ListBuilderTrait>),
-    LineString(Box<dyn ListBuilderTrait>),
-    MultiLineString(Box<dyn ListBuilderTrait>),
-    Polygon(Box<dyn ListBuilderTrait>),
-    MultiPolygon(Box<dyn ListBuilderTrait>),
+    Boolean(BooleanChunkedBuilder),
+    String(StringChunkedBuilder),
+    Point(CoordsBuilder),
+    MultiPoint(CoordsBuilder),
+    LineString(CoordsBuilder),
+    MultiLineString(CoordsBuilder),
+    Polygon(CoordsBuilder),
+    MultiPolygon(CoordsBuilder),
+    Line(CoordsBuilder),
+    Rect(CoordsBuilder),
+    Triangle(CoordsBuilder),
+    GeometryCollection(CollectionBuilder),
+    Geometry(StructBuilder),
 }
 
 impl Builder {
     pub fn new(size: usize) -> Builder {
         Builder::Pending((size, 0))
     }
-    pub fn make_scalar(&mut self) {
-        let (size, skips) = match self {
-            Builder::Pending((size, skips)) => (*size, *skips),
-            _ => panic!("can't make new arr from other than pending"),
-        };
+    /// A builder that writes any geometry into a full GEOMETRY struct.
+    pub fn new_geometry(size: usize, crs: Option<&str>) -> Builder {
+        Builder::Geometry(StructBuilder::new(size, &GeomTypes::ALL, crs))
+    }
+    /// The capacity and leading nulls of a builder that hasn't got a type yet.
+    fn pending(&self) -> PolarsResult<(usize, usize)> {
+        match self {
+            Builder::Pending((size, skips)) => Ok((*size, *skips)),
+            other => polars_bail!(
+                ComputeError: "can't start a new array in a builder that already holds {} values",
+                other.kind()
+            ),
+        }
+    }
+    pub fn make_scalar(&mut self) -> PolarsResult<()> {
+        let (size, skips) = self.pending()?;
         let mut builder = PrimitiveChunkedBuilder::<Float64Type>::new(PlSmallStr::EMPTY, size);
         for _ in 0..skips {
             builder.append_null();
         }
         *self = Builder::Scalar(builder);
+        Ok(())
     }
-    pub fn make_point(&mut self) {
-        let (size, skips) = match self {
-            Builder::Pending((size, skips)) => (*size, *skips),
-            _ => panic!("can't make new arr from other than pending"),
-        };
-        let mut builder = MutableFixedSizeListArray::new_from(
-            MutablePrimitiveArray::with_capacity(size * 2),
-            ArrowDataType::FixedSizeList(
-                Box::new(ArrowField::new(
-                    PlSmallStr::EMPTY,
-                    ArrowDataType::Float64,
-                    false,
-                )),
-                2,
-            ),
-            2,
-        );
-        for _ in 0..skips {
-            builder.push_null();
-        }
-        *self = Builder::Point(builder);
-    }
-    pub fn make_list_arr(&mut self, geom: GeomTypes) {
-        let (size, skips) = match self {
-            Builder::Pending((size, skips)) => (*size, *skips),
-            _ => panic!("can't make new listarr from other than pending"),
-        };
-        match geom {
-            GeomTypes::MultiPoint | GeomTypes::Line | GeomTypes::LineString => {}
-            _ => unimplemented!(),
-        }
-        let mut builder = get_list_builder(
-            &DataType::Array(Box::new(DataType::Float64), 2),
-            size * 2,
-            size,
-            PlSmallStr::EMPTY,
-        );
+    pub fn make_boolean(&mut self) -> PolarsResult<()> {
+        let (size, skips) = self.pending()?;
+        let mut builder = BooleanChunkedBuilder::new(PlSmallStr::EMPTY, size);
         for _ in 0..skips {
             builder.append_null();
         }
-        match geom {
-            GeomTypes::MultiPoint => *self = Builder::MultiPoint(builder),
-            GeomTypes::Line | GeomTypes::LineString => *self = Builder::LineString(builder),
-            _ => unimplemented!(),
-        }
+        *self = Builder::Boolean(builder);
+        Ok(())
     }
-    pub fn make_list_list_arr(&mut self, geom: GeomTypes) {
-        let (size, skips) = match self {
-            Builder::Pending((size, skips)) => (*size, *skips),
-            _ => panic!("can't make new listlistarr from other than pending"),
-        };
-        match geom {
-            GeomTypes::MultiLineString | GeomTypes::Polygon => {}
-            _ => unimplemented!(),
-        }
-        let mut builder = get_list_builder(
-            &DataType::List(Box::new(DataType::Array(Box::new(DataType::Float64), 2))),
-            size * 2,
-            size,
-            PlSmallStr::EMPTY,
-        );
+    pub fn make_string(&mut self) -> PolarsResult<()> {
+        let (size, skips) = self.pending()?;
+        let mut builder = StringChunkedBuilder::new(PlSmallStr::EMPTY, size);
         for _ in 0..skips {
             builder.append_null();
         }
-        match geom {
-            GeomTypes::MultiLineString => *self = Builder::MultiLineString(builder),
-            GeomTypes::Polygon => *self = Builder::Polygon(builder),
-            _ => unimplemented!(),
-        }
+        *self = Builder::String(builder);
+        Ok(())
     }
-    pub fn new_list_list_list_arr(&mut self, geom: GeomTypes) {
-        let (size, skips) = match self {
-            Builder::Pending((size, skips)) => (*size, *skips),
-            _ => panic!("can't make new listlistarr from other than pending"),
+    pub fn make_geometry(&mut self, geom: GeomTypes) -> PolarsResult<()> {
+        let (size, skips) = self.pending()?;
+        let coords = || {
+            let mut builder = CoordsBuilder::new(geom, size);
+            for _ in 0..skips {
+                builder.push_null();
+            }
+            builder
         };
-        match geom {
-            GeomTypes::MultiPolygon => {}
-            _ => unimplemented!(),
-        }
-        let mut builder = get_list_builder(
-            &DataType::List(Box::new(DataType::List(Box::new(DataType::Array(
-                Box::new(DataType::Float64),
-                2,
-            ))))),
-            size * 2,
-            size,
-            PlSmallStr::EMPTY,
-        );
-        for _ in 0..skips {
-            builder.append_null();
+        *self = match geom {
+            GeomTypes::Point => Builder::Point(coords()),
+            GeomTypes::MultiPoint => Builder::MultiPoint(coords()),
+            GeomTypes::LineString => Builder::LineString(coords()),
+            GeomTypes::MultiLineString => Builder::MultiLineString(coords()),
+            GeomTypes::Polygon => Builder::Polygon(coords()),
+            GeomTypes::MultiPolygon => Builder::MultiPolygon(coords()),
+            GeomTypes::Line => Builder::Line(coords()),
+            GeomTypes::Rect => Builder::Rect(coords()),
+            GeomTypes::Triangle => Builder::Triangle(coords()),
+            GeomTypes::GeometryCollection => {
+                let mut builder = CollectionBuilder::new(size);
+                for _ in 0..skips {
+                    builder.push_null();
+                }
+                Builder::GeometryCollection(builder)
+            }
+        };
+        Ok(())
+    }
+    fn kind(&self) -> &'static str {
+        match self {
+            Builder::Pending(_) => "null",
+            Builder::Scalar(_) => "float",
+            Builder::Boolean(_) => "boolean",
+            Builder::String(_) => "string",
+            Builder::Point(_) => "POINT",
+            Builder::MultiPoint(_) => "MULTIPOINT",
+            Builder::LineString(_) => "LINESTRING",
+            Builder::MultiLineString(_) => "MULTILINESTRING",
+            Builder::Polygon(_) => "POLYGON",
+            Builder::MultiPolygon(_) => "MULTIPOLYGON",
+            Builder::Line(_) => "LINE",
+            Builder::Rect(_) => "RECT",
+            Builder::Triangle(_) => "TRIANGLE",
+            Builder::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+            Builder::Geometry(_) => "GEOMETRY",
         }
-        *self = Builder::MultiPolygon(builder)
     }
-    pub fn add_scalar(&mut self, p: f64) {
+    /// Every row of an op has to return the same output type.
+    fn mismatch(&self, value: &str) -> PolarsError {
+        polars_err!(
+            SchemaMismatch: "got a {} value but earlier rows produced {} values",
+            value, self.kind()
+        )
+    }
+    pub fn add_scalar(&mut self, p: f64) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_scalar();
-                self.add_scalar(p);
+                self.make_scalar()?;
+                return self.add_scalar(p);
             }
             Builder::Scalar(builder) => {
                 builder.append_value(p);
             }
-            _ => unimplemented!(),
+            other => return Err(other.mismatch("float")),
         }
+        Ok(())
     }
-    pub fn add_point(&mut self, p: Point) {
+    pub fn add_boolean(&mut self, b: bool) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_point();
-                self.add_point(p);
+                self.make_boolean()?;
+                return self.add_boolean(b);
             }
-            Builder::Point(builder) => {
-                builder
-                    .try_push(Some(vec![Some(p.x()), Some(p.y())]))
-                    .unwrap();
+            Builder::Boolean(builder) => {
+                builder.append_value(b);
             }
-            _ => unimplemented!(),
+            other => return Err(other.mismatch("boolean")),
         }
+        Ok(())
     }
-    pub fn add_multipoint(&mut self, p: MultiPoint) {
+    pub fn add_string(&mut self, v: String) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_list_arr(GeomTypes::MultiPoint);
-                self.add_multipoint(p);
+                self.make_string()?;
+                return self.add_string(v);
             }
-            Builder::MultiPoint(builder) => {
-                let points = p.0;
-                let s = points_to_series(&points).unwrap();
-                builder.append_series(&s).unwrap();
+            Builder::String(builder) => {
+                builder.append_value(v);
             }
-            _ => unimplemented!(),
+            other => return Err(other.mismatch("string")),
         }
+        Ok(())
     }
-    pub fn add_line(&mut self, p: Line) {
-        // TODO: add another type that is a FixedSizeList(FixedSizeList) for this
+    pub fn add_point(&mut self, p: Point) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_list_arr(GeomTypes::Line);
-                self.add_line(p);
+                self.make_geometry(GeomTypes::Point)?;
+                return self.add_point(p);
             }
-            Builder::LineString(builder) => {
-                let points = p.points();
-                let s = points_to_series(&[points.0, points.1]).unwrap();
-                builder.append_series(&s).unwrap();
+            Builder::Point(builder) => builder.push_coords([p.0]),
+            other => return Err(other.mismatch("POINT")),
+        }
+        Ok(())
+    }
+    pub fn add_multipoint(&mut self, p: MultiPoint) -> PolarsResult<()> {
+        match self {
+            Builder::Pending(_) => {
+                self.make_geometry(GeomTypes::MultiPoint)?;
+                return self.add_multipoint(p);
             }
-            _ => unimplemented!(),
+            Builder::MultiPoint(builder) => builder.push_coords(p.into_iter().map(|p| p.0)),
+            other => return Err(other.mismatch("MULTIPOINT")),
         }
+        Ok(())
     }
-    pub fn add_linestring(&mut self, p: LineString) {
+    pub fn add_line(&mut self, p: Line) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_list_arr(GeomTypes::LineString);
-                self.add_linestring(p);
+                self.make_geometry(GeomTypes::Line)?;
+                return self.add_line(p);
             }
-            Builder::LineString(builder) => {
-                let points = p.into_points();
-                let s = points_to_series(&points).unwrap();
-                builder.append_series(&s).unwrap();
+            Builder::Line(builder) => builder.push_coords([p.start, p.end]),
+            other => return Err(other.mismatch("LINE")),
+        }
+        Ok(())
+    }
+    pub fn add_rect(&mut self, p: Rect) -> PolarsResult<()> {
+        match self {
+            Builder::Pending(_) => {
+                self.make_geometry(GeomTypes::Rect)?;
+                return self.add_rect(p);
             }
-            _ => unimplemented!(),
+            Builder::Rect(builder) => builder.push_coords([p.min(), p.max()]),
+            other => return Err(other.mismatch("RECT")),
         }
+        Ok(())
     }
-    pub fn add_multi_line_string(&mut self, p: MultiLineString) {
+    pub fn add_triangle(&mut self, p: Triangle) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_list_list_arr(GeomTypes::MultiLineString);
-                self.add_multi_line_string(p);
+                self.make_geometry(GeomTypes::Triangle)?;
+                return self.add_triangle(p);
             }
-            Builder::MultiLineString(builder) => {
-                let linestrings = p.0;
-                let lil_size = linestrings.len();
-                let mut lil_builder = Builder::new(lil_size);
-                linestrings.into_iter().for_each(|l| {
-                    lil_builder.add_linestring(l);
-                });
-                let s = lil_builder.finish();
-                builder.append_series(&s).unwrap();
+            Builder::Triangle(builder) => builder.push_coords(p.to_array()),
+            other => return Err(other.mismatch("TRIANGLE")),
+        }
+        Ok(())
+    }
+    pub fn add_geometry_collection(&mut self, p: GeometryCollection) -> PolarsResult<()> {
+        match self {
+            Builder::Pending(_) => {
+                self.make_geometry(GeomTypes::GeometryCollection)?;
+                return self.add_geometry_collection(p);
             }
-            _ => unimplemented!(),
+            Builder::GeometryCollection(builder) => builder.push(p)?,
+            other => return Err(other.mismatch("GEOMETRYCOLLECTION")),
         }
+        Ok(())
     }
-    pub fn add_polygon(&mut self, p: Polygon) {
+    pub fn add_linestring(&mut self, p: LineString) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_list_list_arr(GeomTypes::Polygon);
-                self.add_polygon(p);
+                self.make_geometry(GeomTypes::LineString)?;
+                return self.add_linestring(p);
+            }
+            Builder::LineString(builder) => builder.push_coords(p.0),
+            other => return Err(other.mismatch("LINESTRING")),
+        }
+        Ok(())
+    }
+    pub fn add_multi_line_string(&mut self, p: MultiLineString) -> PolarsResult<()> {
+        match self {
+            Builder::Pending(_) => {
+                self.make_geometry(GeomTypes::MultiLineString)?;
+                return self.add_multi_line_string(p);
             }
             Builder::MultiLineString(builder) => {
-                let exterior = p.exterior().to_owned();
-                let interiors = p.interiors();
-                let size = interiors.len() + 1;
-                let mut inner_lines = Builder::new(size);
-                inner_lines.add_linestring(exterior);
-                interiors.into_iter().for_each(|l| {
-                    inner_lines.add_linestring(l.to_owned());
-                });
-                let s = inner_lines.finish();
-                builder.append_series(&s).unwrap();
+                p.into_iter().for_each(|l| builder.push_coords(l.0));
+                builder.close(0);
             }
-            _ => unimplemented!(),
+            other => return Err(other.mismatch("MULTILINESTRING")),
         }
+        Ok(())
     }
-    pub fn add_multi_polygon(&mut self, p: MultiPolygon) {
+    pub fn add_polygon(&mut self, p: Polygon) -> PolarsResult<()> {
         match self {
             Builder::Pending(_) => {
-                self.make_list_list_arr(GeomTypes::MultiPolygon);
-                self.add_multi_polygon(p);
+                self.make_geometry(GeomTypes::Polygon)?;
+                return self.add_polygon(p);
+            }
+            Builder::Polygon(builder) => {
+                push_rings(builder, p);
+                builder.close(0);
+            }
+            other => return Err(other.mismatch("POLYGON")),
+        }
+        Ok(())
+    }
+    pub fn add_multi_polygon(&mut self, p: MultiPolygon) -> PolarsResult<()> {
+        match self {
+            Builder::Pending(_) => {
+                self.make_geometry(GeomTypes::MultiPolygon)?;
+                return self.add_multi_polygon(p);
             }
             Builder::MultiPolygon(builder) => {
-                let polygons = p.0;
-                let size = polygons.len();
-                let mut inner_polys = Builder::new(size);
-                polygons.into_iter().for_each(|poly| {
-                    inner_polys.add_polygon(poly.to_owned());
+                p.into_iter().for_each(|poly| {
+                    push_rings(builder, poly);
+                    builder.close(1);
                 });
-                let s = inner_polys.finish();
-                builder.append_series(&s).unwrap();
+                builder.close(0);
             }
-            _ => unimplemented!(),
+            other => return Err(other.mismatch("MULTIPOLYGON")),
         }
+        Ok(())
     }
     pub fn add_null(&mut self) {
         match self {
             Builder::Pending((_, skips)) => *skips += 1,
-            Builder::Point(builder) => {
-                builder.push_null();
-            }
-            Builder::MultiPoint(builder)
+            Builder::Point(builder)
+            | Builder::MultiPoint(builder)
             | Builder::LineString(builder)
             | Builder::MultiLineString(builder)
             | Builder::Polygon(builder)
-            | Builder::MultiPolygon(builder) => {
-                builder.append_null();
-            }
+            | Builder::MultiPolygon(builder)
+            | Builder::Line(builder)
+            | Builder::Rect(builder)
+            | Builder::Triangle(builder) => builder.push_null(),
+            Builder::GeometryCollection(builder) => builder.push_null(),
             Builder::Scalar(builder) => builder.append_null(),
-            _ => unimplemented!(),
+            Builder::Boolean(builder) => builder.append_null(),
+            Builder::String(builder) => builder.append_null(),
+            Builder::Geometry(builder) => builder.add_null(),
         }
     }
-    pub fn add(&mut self, value: GeomOpResult) {
+    pub fn add(&mut self, value: GeomOpResult) -> PolarsResult<()> {
+        if let Builder::Geometry(builder) = self {
+            return builder.add(value);
+        }
         match value {
             GeomOpResult::Point(p) => self.add_point(p),
             GeomOpResult::MultiPoint(ps) => self.add_multipoint(ps),
@@ -382,20 +726,129 @@ impl Builder {
             GeomOpResult::MultiLineString(ml) => self.add_multi_line_string(ml),
             GeomOpResult::Polygon(poly) => self.add_polygon(poly),
             GeomOpResult::MultiPolygon(mpoly) => self.add_multi_polygon(mpoly),
+            GeomOpResult::Rect(r) => self.add_rect(r),
+            GeomOpResult::Triangle(t) => self.add_triangle(t),
+            GeomOpResult::GeometryCollection(gc) => self.add_geometry_collection(gc),
             GeomOpResult::Float(val) => self.add_scalar(val),
-            GeomOpResult::Null => self.add_null(),
+            GeomOpResult::Boolean(val) => self.add_boolean(val),
+            GeomOpResult::String(val) => self.add_string(val),
+            GeomOpResult::Null => {
+                self.add_null();
+                Ok(())
+            }
+            GeomOpResult::Error(e) => Err(e),
         }
     }
-    pub fn finish(self) -> Series {
-        match self {
-            Builder::Point(mut builder) => Series::from_arrow(PlSmallStr::EMPTY, builder.as_box()).unwrap(),
-            Builder::MultiPoint(mut builder)
-            | Builder::LineString(mut builder)
-            | Builder::MultiLineString(mut builder)
-            | Builder::Polygon(mut builder)
-            | Builder::MultiPolygon(mut builder) => builder.finish().into_series(),
+    /// A builder that only saw nulls becomes a Null dtype Series.
+    pub fn finish(self) -> PolarsResult<Series> {
+        let s = match self {
+            Builder::Point(builder)
+            | Builder::MultiPoint(builder)
+            | Builder::LineString(builder)
+            | Builder::MultiLineString(builder)
+            | Builder::Polygon(builder)
+            | Builder::MultiPolygon(builder)
+            | Builder::Line(builder)
+            | Builder::Rect(builder)
+            | Builder::Triangle(builder) => builder.finish()?,
+            Builder::GeometryCollection(builder) => builder.finish()?,
             Builder::Scalar(builder) => builder.finish().into_series(),
-            Builder::Pending(_) => panic!("can't finish pending builder"),
+            Builder::Boolean(builder) => builder.finish().into_series(),
+            Builder::String(builder) => builder.finish().into_series(),
+            Builder::Geometry(builder) => builder.finish(PlSmallStr::EMPTY)?,
+            Builder::Pending((_, skips)) => {
+                Series::full_null(PlSmallStr::EMPTY, skips, &DataType::Null)
+            }
+        };
+        Ok(s)
+    }
+    /// Like `finish` but a builder that only saw nulls becomes a null Series of `dtype`.
+    pub fn finish_typed(self, dtype: &DataType) -> PolarsResult<Series> {
+        match self {
+            Builder::Pending((_, skips)) => Ok(Series::full_null(PlSmallStr::EMPTY, skips, dtype)),
+            builder => builder.finish(),
+        }
+    }
+}
+
+/// Pushes the exterior then the interiors, each as one list of coordinates.
+fn push_rings(builder: &mut CoordsBuilder, polygon: Polygon) {
+    let (exterior, interiors) = polygon.into_inner();
+    builder.push_coords(exterior.0);
+    interiors
+        .into_iter()
+        .for_each(|ring| builder.push_coords(ring.0));
+}
+
+/// Writes geometries of any type into a struct with one field per geometry type,
+/// each row has at most one non-null field.
+pub struct StructBuilder {
+    len: usize,
+    fields: Vec<(GeomTypes, Builder)>,
+    crs: Option<PlSmallStr>,
+}
+
+impl StructBuilder {
+    pub fn new(size: usize, geom_types: &[GeomTypes], crs: Option<&str>) -> StructBuilder {
+        StructBuilder {
+            len: 0,
+            fields: geom_types
+                .iter()
+                .map(|geom| (*geom, Builder::new(size)))
+                .collect(),
+            crs: crs.map(PlSmallStr::from),
         }
     }
+    pub fn add(&mut self, value: GeomOpResult) -> PolarsResult<()> {
+        let value = match value {
+            GeomOpResult::Error(e) => return Err(e),
+            value => value,
+        };
+        let geom_type = value.geom_type()?;
+        let mut value = Some(value);
+        for (geom, builder) in self.fields.iter_mut() {
+            match value.take_if(|_| Some(*geom) == geom_type) {
+                Some(value) => builder.add(value)?,
+                None => builder.add_null(),
+            }
+        }
+        self.len += 1;
+        Ok(())
+    }
+    pub fn add_null(&mut self) {
+        self.fields
+            .iter_mut()
+            .for_each(|(_, builder)| builder.add_null());
+        self.len += 1;
+    }
+    pub fn finish(self, name: PlSmallStr) -> PolarsResult<Series> {
+        let crs = self.crs.as_deref();
+        let fields = self
+            .fields
+            .into_iter()
+            .map(|(geom, builder)| {
+                Ok(builder
+                    .finish_typed(&geom.dtype())?
+                    .with_name(geom.field_name(crs)))
+            })
+            .collect::<PolarsResult<Vec<Series>>>()?;
+        Ok(StructChunked::from_series(name, self.len, fields.iter())?.into_series())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::point;
+
+    #[test]
+    fn mixing_values_and_geometries_is_an_error() {
+        let mut builder = Builder::new(2);
+        builder.add(GeomOpResult::Float(1.)).unwrap();
+        assert!(builder.add(point!(x: 1., y: 1.).into()).is_err());
+
+        let mut builder = Builder::new(2);
+        builder.add(point!(x: 1., y: 1.).into()).unwrap();
+        assert!(builder.add(GeomOpResult::Boolean(true)).is_err());
+    }
 }